@@ -1,7 +1,5 @@
 use crate::enums::chess_error::ChessError;
 use crate::functions::{
-    get_letter,
-    get_number,
     pos_to_num,
     num_to_pos
 };
@@ -10,34 +8,38 @@ use crate::traits::{
     chess_board_contract::ChessBoardContract
 };
 
-use crate::board_types::normalboard::{
-    ChessPiece,
-    NormalBoard
-};
+use crate::board_types::normalboard::NormalBoard;
 
 use crate::enums::{
     piece_color::PieceColor,
     end_type::EndType,
+    tie_type::TieType,
     piece_num::PieceNum,
-    piece_type::PieceType
+    piece_type::{PieceType, PROMOTION_PIECES}
 };
 
-use std::collections::HashMap;
-use rustc_hash::FxHashMap;
-use std::sync::Arc;
+use crate::board_types::magic::MagicTable;
+use crate::board_types::position::{
+    Position,
+    WHITE_KING_SIDE_CASTLE,
+    WHITE_QUEEN_SIDE_CASTLE,
+    BLACK_KING_SIDE_CASTLE,
+    BLACK_QUEEN_SIDE_CASTLE,
+    ALL_CASTLING_RIGHTS,
+    FIFTY_MOVE_RULE_PLIES,
+    MoveUndo
+};
+use crate::board_types::chess_move::{Move, DOUBLE_PAWN_PUSH, EN_PASSANT, CASTLING};
 
-pub type BitBoard = [u64; 12];
-pub type BitBoardMove = (((u64, u64), (u64, u64)), BitBoard);
+pub type BitBoard = Position;
 
 #[derive(Clone)]
 pub struct Constants {
     pub start_board: BitBoard,
-    pub row_and_column_mask: [u64; 64],
-    pub diagonal_mask: [u64; 64],
     pub pawn_reach: [[u64; 64]; 2],
-    pub rook_reach: [FxHashMap<u64, u64>; 64],
+    pub rook_reach: MagicTable,
     pub knight_reach: [u64; 64],
-    pub bishop_reach: [FxHashMap<u64, u64>; 64],
+    pub bishop_reach: MagicTable,
     pub king_reach: [u64; 64]
 }
 
@@ -45,52 +47,18 @@ impl Constants {
     pub fn new() -> Self {
         Self {
             start_board: generate_start_board(),
-            row_and_column_mask: generate_row_and_column_mask(),
-            diagonal_mask: generate_diagonal_mask(),
             pawn_reach: generate_pawn_reach(),
-            rook_reach: generate_rook_reach(),
+            rook_reach: MagicTable::rook(),
             knight_reach: generate_knight_reach(),
-            bishop_reach: generate_bishop_reach(),
+            bishop_reach: MagicTable::bishop(),
             king_reach: generate_king_reach()
         }
     }
 }
 
-pub fn generate_all_possible_configurations(input_board: u64) -> Vec<u64> {
-    let mut configurations: Vec<u64> = Vec::with_capacity(100_000);
-    let mut points: Vec<u64> = Vec::with_capacity(100);
-
-    let mut tmp = input_board;
-    while tmp > 0 {
-        let point = tmp.trailing_zeros() as u64;
-        points.push(point);
-        tmp -= 1 << point;
-    }
-
-    configurations.push(0);
-
-    let mut board = 0;
-    let mut count = 0;
-    while count < points.len() {
-        let num_pos = points[count];
-        if board & (1 << num_pos) == 0 {
-            board += 1 << num_pos;
-            for l in 0..count {
-                let num_pos = points[l];
-                board -= 1 << num_pos;
-            }
-            configurations.push(board);
-            count = 0;
-        } else {
-            count += 1;
-        }
-    }
-
-    configurations
-}
-
 pub fn generate_start_board() -> BitBoard {
-    let mut board = [0; 12];
+    let mut board = BitBoard::new_empty_board();
+    board.castling_rights = ALL_CASTLING_RIGHTS;
 
     // White pawns
     for i in 0..8 {
@@ -138,148 +106,9 @@ pub fn generate_start_board() -> BitBoard {
     // Black king
     board[PieceNum::BlackKing as usize] += 1 << pos_to_num(4, 7);
 
-    board
-}
-
-pub fn generate_row_and_column_mask() -> [u64; 64] {
-    let mut possible_moves = [0; 64];
-
-    for i in 0..64 {
-        let (letter, number) = num_to_pos(i);
-        let mut tmp = 0;
-
-        // Up
-        for j in number + 1..8 {
-            tmp += 1 << pos_to_num(letter, j);
-        }
-
-        // Down
-        for j in 0..number {
-            tmp += 1 << pos_to_num(letter, j);
-        }
-
-        // Left
-        for j in 0..letter {
-            tmp += 1 << pos_to_num(j, number);
-        }
-
-        // Right
-        for j in letter + 1..8 {
-            tmp += 1 << pos_to_num(j, number);
-        }
-
-        possible_moves[i as usize] = tmp;
-    }
-
-
-    possible_moves
-}
-
-// pub fn generate_double_row_and_column_mask(row_column_mask: [u64; 64]) ->  HashMap<u64, u64> { //HashMap<u64, u64> {
-//     let mut mask_map: HashMap<u64, u64> = HashMap::with_capacity(2081);
-
-//     let mut possibilities = [0; 2081];
-
-//     // Index starts from 1 because you can have zero pieces of the type on the board
-//     for i in 0..64 {
-//         possibilities[i + 1] = 1 << i;
-//     }
-
-//     let mut index = 65;
-//     for i in 1..64 {
-//         for j in 0..i {
-//             possibilities[index] = (1 << i) + (1 << j);
-//             index += 1;
-//         }
-//     }
-
-//     mask_map.insert(0, 0);
-
-//     for possibility in &possibilities[1 .. 64] {
-//         mask_map.insert(*possibility, row_column_mask[possibility.trailing_zeros() as usize]);
-//     }
-
-//     for possibility in &possibilities[65 .. ] {
-//         let first_pos = (*possibility as u64).trailing_zeros() as usize;
-//         let second_pos = ((*possibility - (1 << first_pos)) as u64).trailing_zeros() as usize;
-
-//         mask_map.insert(*possibility, row_column_mask[first_pos] | row_column_mask[second_pos]);
-//     }
-
-
-//     // let mut test = std::collections::HashSet::with_capacity(300_000_000);
-//     // // 268_435_456
-//     // // let mut test = 0;
-//     // for mov in &mask_map {
-//     //     let (_, mask) = match mov {
-//     //         (a, b) => (*a, *b)
-//     //     };
-
-//     //     let configurations = generate_all_possible_configurations(mask);
-        
-//     //     // test += configurations.len() as u64;
-//     //     for item in configurations {
-//     //         test.insert(item);
-//     //     }
-//     // }
-
-//     // println!("{}", test.len());
-
-//     mask_map
-// }
-
-fn generate_diagonal_mask() -> [u64; 64] {
-    let mut possible_moves = [0; 64];
-
-    for i in 0..64 {
-        let (letter, number) = num_to_pos(i);
-        let mut tmp = 0;
-
-        // Up-right
-        for j in 1..7 {
-            let new_letter = letter as i32 + j;
-            let new_number = number as i32 + j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            tmp += 1 << pos_to_num(new_letter as u64, new_number as u64);
-        }
-
-        // Down-right
-        for j in 1..7 {
-            let new_letter = letter as i32 + j;
-            let new_number = number as i32 - j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            tmp += 1 << pos_to_num(new_letter as u64, new_number as u64);
-        }
-
-        // Down-left
-        for j in 1..7 {
-            let new_letter = letter as i32 - j;
-            let new_number = number as i32 - j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            tmp += 1 << pos_to_num(new_letter as u64, new_number as u64);
-        }
-
-        // Up-left
-        for j in 1..7 {
-            let new_letter = letter as i32 - j;
-            let new_number = number as i32 + j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            tmp += 1 << pos_to_num(new_letter as u64, new_number as u64);
-        }
-
-        possible_moves[i as usize] = tmp;
-    }
-
+    board.hash = board.compute_hash();
 
-    possible_moves
+    board
 }
 
 // First = White, Second = Black
@@ -317,98 +146,6 @@ fn generate_pawn_reach() -> [[u64; 64]; 2] {
     possible_moves
 }
 
-fn generate_rook_reach() -> [FxHashMap<u64, u64>; 64] {
-    let mut possible_moves: [FxHashMap<u64, u64>; 64] = [(); 64].map(|_| FxHashMap::default());
-
-    for i in 0..64 {
-        let (letter, number) = num_to_pos(i);
-        let mut possibilities = Vec::with_capacity(16384);
-        possibilities.push(0);
-        let mut points = Vec::with_capacity(14);
-
-        // Up
-        for j in number + 1..8 {
-            points.push(pos_to_num(letter, j));
-        }
-
-        // Down
-        for j in 0..number {
-            points.push(pos_to_num(letter, j));
-        }
-
-        // Left
-        for j in 0..letter {
-            points.push(pos_to_num(j, number));
-        }
-
-        // Right
-        for j in letter + 1..8 {
-            points.push(pos_to_num(j, number));
-        }
-
-        let mut board = 0;
-        let mut count = 0;
-        while count < points.len() {
-            let num_pos = points[count];
-            if board & (1 << num_pos) == 0 {
-                board += 1 << num_pos;
-                for l in 0..count {
-                    let num_pos = points[l];
-                    board -= 1 << num_pos;
-                }
-                possibilities.push(board);
-                count = 0;
-            } else {
-                count += 1;
-            }
-        }
-
-        for p in possibilities {
-            let mut board = 0;
-
-            // Up
-            for j in number + 1..8 {
-                let pos = 1 << pos_to_num(letter, j);
-                board += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            // Down
-            for j in (0..number).rev() {
-                let pos = 1 << pos_to_num(letter, j);
-                board += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            // Left
-            for j in (0..letter).rev() {
-                let pos = 1 << pos_to_num(j, number);
-                board += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            // Right
-            for j in letter + 1..8 {
-                let pos = 1 << pos_to_num(j, number);
-                board += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            possible_moves[i as usize].insert(p, board);
-        }
-    }
-
-    possible_moves
-}
-
 fn generate_knight_reach() -> [u64; 64] {
     let mut possible_moves = [0; 64];
 
@@ -434,138 +171,6 @@ fn generate_knight_reach() -> [u64; 64] {
     possible_moves
 }
 
-fn generate_bishop_reach() -> [FxHashMap<u64, u64>; 64] {
-    let mut possible_moves: [FxHashMap<u64, u64>; 64] = [(); 64].map(|_| FxHashMap::default());
-
-    for i in 0..64 {
-        let (letter, number) = num_to_pos(i);
-        let mut possibilities = Vec::with_capacity(16384);
-        possibilities.push(0);
-        let mut points = Vec::with_capacity(14);
-
-        // Up-right
-        for j in 1..7 {
-            let new_letter = letter as i32 + j;
-            let new_number = number as i32 + j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            points.push(pos_to_num(new_letter as u64, new_number as u64));
-        }
-
-        // Down-right
-        for j in 1..7 {
-            let new_letter = letter as i32 + j;
-            let new_number = number as i32 - j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            points.push(pos_to_num(new_letter as u64, new_number as u64));
-        }
-
-        // Down-left
-        for j in 1..7 {
-            let new_letter = letter as i32 - j;
-            let new_number = number as i32 - j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            points.push(pos_to_num(new_letter as u64, new_number as u64));
-        }
-
-        // Up-left
-        for j in 1..7 {
-            let new_letter = letter as i32 - j;
-            let new_number = number as i32 + j;
-            if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                break;
-            }
-            points.push(pos_to_num(new_letter as u64, new_number as u64));
-        }
-
-        let mut board = 0;
-        let mut count = 0;
-        while count < points.len() {
-            let num_pos = points[count];
-            if board & (1 << num_pos) == 0 {
-                board += 1 << num_pos;
-                for l in 0..count {
-                    let num_pos = points[l];
-                    board -= 1 << num_pos;
-                }
-                possibilities.push(board);
-                count = 0;
-            } else {
-                count += 1;
-            }
-        }
-
-        for p in possibilities {
-            let mut b = 0;
-
-            // Up-right
-            for j in 1..7 {
-                let new_letter = letter as i32 + j;
-                let new_number = number as i32 + j;
-                if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                    break;
-                }
-                let pos = 1 << pos_to_num(new_letter as u64, new_number as u64);
-                b += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            // Down-right
-            for j in 1..7 {
-                let new_letter = letter as i32 + j;
-                let new_number = number as i32 - j;
-                if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                    break;
-                }
-                let pos = 1 << pos_to_num(new_letter as u64, new_number as u64);
-                b += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            // Down-left
-            for j in 1..7 {
-                let new_letter = letter as i32 - j;
-                let new_number = number as i32 - j;
-                if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                    break;
-                }
-                let pos = 1 << pos_to_num(new_letter as u64, new_number as u64);
-                b += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            // Up-left
-            for j in 1..7 {
-                let new_letter = letter as i32 - j;
-                let new_number = number as i32 + j;
-                if 0 > new_letter || new_letter > 7 || 0 > new_number || new_number > 7 {
-                    break;
-                }
-                let pos = 1 << pos_to_num(new_letter as u64, new_number as u64);
-                b += pos;
-                if p & pos == pos {
-                    break;
-                }
-            }
-
-            possible_moves[i as usize].insert(p, b);
-        }
-    }
-
-    possible_moves
-}
-
 fn generate_king_reach() -> [u64; 64] {
     let mut possible_moves = [0; 64];
 
@@ -591,13 +196,6 @@ fn generate_king_reach() -> [u64; 64] {
     possible_moves
 }
 
-pub fn print_bitboard(num: u64) {
-    let board = format!("{:#066b}", num).chars().rev().collect::<String>();
-    for i in (0..8).rev() {
-        println!("{}", board[i*8..i*8+8].to_string());
-    }
-}
-
 fn get_full_color_board(board: &BitBoard, color: PieceColor) -> u64 {
     let mut possible_moves: u64 = 0;
     match color {
@@ -625,496 +223,187 @@ fn get_occupied_board(board: &BitBoard) -> u64 {
     get_full_color_board(board, PieceColor::White) | get_full_color_board(board, PieceColor::Black)
 }
 
-pub fn get_reach_board(board: &BitBoard, color: PieceColor, constants: &Constants) -> u64 {
+// Whether any piece of the given color attacks the square
+pub fn is_square_attacked(board: &BitBoard, square: usize, by_color: PieceColor, constants: &Constants) -> bool {
+    let offset = by_color as usize * 6;
     let occupied_board = get_occupied_board(board);
-
-    let mut b = 0;
-
-    match color {
-        PieceColor::White => {
-            for i in 0..64 {
-                let num = 1 << i;
-                let rows_and_columns = occupied_board & constants.row_and_column_mask[i];
-                let diagonals = occupied_board & constants.diagonal_mask[i];
-
-                if board[PieceNum::WhitePawn as usize] & num == num {
-                    b |= constants.pawn_reach[0][i];
-                } else if board[PieceNum::WhiteRook as usize] & num == num {
-                    b |= constants.rook_reach[i].get(&rows_and_columns).unwrap();
-                } else if board[PieceNum::WhiteKnight as usize] & num == num {
-                    b |= constants.knight_reach[i];
-                } else if board[PieceNum::WhiteBishop as usize] & num == num {
-                    b |= constants.bishop_reach[i].get(&diagonals).unwrap();
-                } else if board[PieceNum::WhiteQueen as usize] & num == num {
-                    b |= constants.rook_reach[i].get(&rows_and_columns).unwrap();
-                    b |= constants.bishop_reach[i].get(&diagonals).unwrap();
-                } else if board[PieceNum::WhiteKing as usize] & num == num {
-                    b |= constants.king_reach[i];
-                }
-            }
-        },
-        PieceColor::Black => {
-            for i in 0..64 {
-                let num = 1 << i;
-                let rows_and_columns = occupied_board & constants.row_and_column_mask[i];
-                let diagonals = occupied_board & constants.diagonal_mask[i];
-
-                if board[PieceNum::BlackPawn as usize] & num == num {
-                    b |= constants.pawn_reach[1][i];
-                } else if board[PieceNum::BlackRook as usize] & num == num {
-                    b |= constants.rook_reach[i].get(&rows_and_columns).unwrap();
-                } else if board[PieceNum::BlackKnight as usize] & num == num {
-                    b |= constants.knight_reach[i];
-                } else if board[PieceNum::BlackBishop as usize] & num == num {
-                    b |= constants.bishop_reach[i].get(&diagonals).unwrap();
-                } else if board[PieceNum::BlackQueen as usize] & num == num {
-                    b |= constants.rook_reach[i].get(&rows_and_columns).unwrap();
-                    b |= constants.bishop_reach[i].get(&diagonals).unwrap();
-                } else if board[PieceNum::BlackKing as usize] & num == num {
-                    b |= constants.king_reach[i];
-                }
-            }
-        }
-    }
-
-    b
+    let queens = board[offset + PieceType::Queen as usize];
+
+    // A pawn attacks the square if a pawn of the other color on the square would attack it back
+    constants.pawn_reach[by_color.opposite_color() as usize][square] & board[offset + PieceType::Pawn as usize] != 0
+        || constants.knight_reach[square] & board[offset + PieceType::Knight as usize] != 0
+        || constants.king_reach[square] & board[offset + PieceType::King as usize] != 0
+        || constants.rook_reach.get(square, occupied_board) & (board[offset + PieceType::Rook as usize] | queens) != 0
+        || constants.bishop_reach.get(square, occupied_board) & (board[offset + PieceType::Bishop as usize] | queens) != 0
 }
 
 fn is_in_check(board: &BitBoard, color: PieceColor, constants: &Constants) -> bool {
-    let opposite_reach_board = get_reach_board(&board, color.opposite_color(), constants);
-    match color {
-        PieceColor::White => {
-            let king_num = board[PieceNum::WhiteKing as usize];
-            opposite_reach_board & king_num == king_num
-        },
-        PieceColor::Black => {
-            let king_num = board[PieceNum::BlackKing as usize];
-            opposite_reach_board & king_num == king_num
-        }
-    }
+    let king = board[color as usize * 6 + PieceType::King as usize];
+    is_square_attacked(board, king.trailing_zeros() as usize, color.opposite_color(), constants)
 }
 
-pub fn generate_possible_moves(board: &BitBoard, prev_board: Option<&BitBoard>, color: PieceColor, constants: &Constants) -> Vec<BitBoardMove> {
-    let mut possible_moves = Vec::new();
-
-    let opposite_color = color.opposite_color();
-    let occupied_board = get_occupied_board(&board);
-    let own_pieces = get_full_color_board(&board, color);
-    let opposite_pieces = get_full_color_board(&board, opposite_color);
-    let reach_board = get_reach_board(&board, color, constants);
-    let opposite_reach_board = get_reach_board(&board, opposite_color, constants);
-
-    match color {
-        PieceColor::White => {
-            // Pawns
-            let mut tmp = board[PieceNum::WhitePawn as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                if occupied_board & 1 << (i + 8) == 0 {
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::WhitePawn as usize] -= 1 << i;
-                    if 55 <= i + 8 && i + 8 < 64 {
-                        new_board[PieceNum::WhiteQueen as usize] += 1 << (i + 8);
-                    } else {
-                        new_board[PieceNum::WhitePawn as usize] += 1 << (i + 8);
-                    }
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 + 8)), new_board));
-
-                    if 7 <= i && i < 16 && occupied_board & 1 << (i + 16) == 0 {
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhitePawn as usize] -= 1 << i;
-                        new_board[PieceNum::WhitePawn as usize] += 1 << (i + 16);
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 + 16)), new_board));
-                    }
-                }
-
-                let mut possible_attacks = constants.pawn_reach[0][i as usize] & opposite_pieces;
-                while possible_attacks != 0 {
-                    let j = possible_attacks.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::WhitePawn as usize] -= 1 << i;
-                    new_board[PieceNum::WhitePawn as usize] += 1 << j;
-
-                    new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    possible_attacks -= 1 << j;
-                }
-
-                tmp -= 1 << i;
-            }
-
-            // Rooks
-            let mut tmp = board[PieceNum::WhiteRook as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::WhiteRook as usize] -= 1 << i;
-                    new_board[PieceNum::WhiteRook as usize] += 1 << j;
-
-                    new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
-
-                tmp -= 1 << i;
-            }
-
-            // Knights
-            let mut tmp = board[PieceNum::WhiteKnight as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable = constants.knight_reach[i as usize];
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::WhiteKnight as usize] -= 1 << i;
-                    new_board[PieceNum::WhiteKnight as usize] += 1 << j;
-
-                    new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
-
-                tmp -= 1 << i;
-            }
-
-            // Bishops
-            let mut tmp = board[PieceNum::WhiteBishop as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::WhiteBishop as usize] -= 1 << i;
-                    new_board[PieceNum::WhiteBishop as usize] += 1 << j;
-
-                    new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
-
-                tmp -= 1 << i;
-            }
-
-            // Queen
-            let mut tmp = board[PieceNum::WhiteQueen as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable_straight = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                let reachable_diagonals = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                let reachable = reachable_straight | reachable_diagonals;
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::WhiteQueen as usize] -= 1 << i;
-                    new_board[PieceNum::WhiteQueen as usize] += 1 << j;
-
-                    new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
-
-                tmp -= 1 << i;
-            }
-
-            // King
-            let mut tmp = board[PieceNum::WhiteKing as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable = constants.king_reach[i as usize];
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::WhiteKing as usize] -= 1 << i;
-                    new_board[PieceNum::WhiteKing as usize] += 1 << j;
-
-                    new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
-
-                tmp -= 1 << i;
-            }
-        },
-        PieceColor::Black => {
-            // Pawns
-            let mut tmp = board[PieceNum::BlackPawn as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                if occupied_board & 1 << (i - 8) == 0 {
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::BlackPawn as usize] -= 1 << i;
-                    if 0 <= i - 8 && i - 8 < 8 {
-                        new_board[PieceNum::BlackQueen as usize] += 1 << (i - 8);
-                    } else {
-                        new_board[PieceNum::BlackPawn as usize] += 1 << (i - 8);
-                    }
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 - 8)), new_board));
-
-                    if 48 <= i && i < 56 && occupied_board & 1 << (i - 16) == 0 {
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackPawn as usize] -= 1 << i;
-                        new_board[PieceNum::BlackPawn as usize] += 1 << (i - 16);
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 - 16)), new_board));
-                    }
-                }
-
-                let mut possible_attacks = constants.pawn_reach[1][i as usize] & opposite_pieces;
-                while possible_attacks != 0 {
-                    let j = possible_attacks.trailing_zeros();
+// The moves of the player whose turn it is, without the ones that leave their own king in check
+pub fn generate_legal_moves(board: &BitBoard, constants: &Constants) -> Vec<Move> {
+    filter_legal_moves(board, generate_pseudo_legal_moves(board, constants, false), constants)
+}
 
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::BlackPawn as usize] -= 1 << i;
-                    new_board[PieceNum::BlackPawn as usize] += 1 << j;
+// Captures, including en passant, and promotions
+pub fn generate_legal_captures(board: &BitBoard, constants: &Constants) -> Vec<Move> {
+    filter_legal_moves(board, generate_pseudo_legal_moves(board, constants, true), constants)
+}
 
-                    new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
+fn filter_legal_moves(board: &BitBoard, mut res: Vec<Move>, constants: &Constants) -> Vec<Move> {
+    let color = board.turn;
+    let mut board = *board;
 
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    possible_attacks -= 1 << j;
-                }
+    res.retain(|mov| {
+        let undo = board.make_move(*mov);
+        let legal = !is_in_check(&board, color, constants);
+        board.unmake_move(*mov, undo);
+        legal
+    });
 
-                tmp -= 1 << i;
-            }
+    res
+}
 
-            // Rooks
-            let mut tmp = board[PieceNum::BlackRook as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
+fn generate_pseudo_legal_moves(board: &BitBoard, constants: &Constants, captures_only: bool) -> Vec<Move> {
+    let mut possible_moves = Vec::with_capacity(match captures_only {
+        true => 16,
+        false => 64
+    });
 
-                let reachable = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                let mut moveable = reachable & !own_pieces;
+    let color = board.turn;
+    let offset = color as usize * 6;
+    let opposite_offset = color.opposite_color() as usize * 6;
+    let occupied_board = get_occupied_board(board);
+    let own_pieces = get_full_color_board(board, color);
+    let opposite_pieces = get_full_color_board(board, color.opposite_color());
 
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
+    // Pawns, with the rows a double step can start from and the rows they are promoted on
+    let (forward, start_row, last_row): (i32, u64, u64) = match color {
+        PieceColor::White => (8, 0xFF << 8, 0xFF << 56),
+        PieceColor::Black => (-8, 0xFF << 48, 0xFF)
+    };
 
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::BlackRook as usize] -= 1 << i;
-                    new_board[PieceNum::BlackRook as usize] += 1 << j;
+    let mut tmp = board[offset + PieceType::Pawn as usize];
+    while tmp != 0 {
+        let i = tmp.trailing_zeros();
+        tmp &= tmp - 1;
 
-                    new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
+        let mut moveable = constants.pawn_reach[color as usize][i as usize] & (opposite_pieces | board.en_passant);
 
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
+        // Only steps that promote the pawn count with captures_only
+        let one_step = (i as i32 + forward) as u32;
+        if occupied_board & (1 << one_step) == 0 && (!captures_only || last_row & (1 << one_step) != 0) {
+            moveable |= 1 << one_step;
 
-                tmp -= 1 << i;
+            let two_steps = (one_step as i32 + forward) as u32;
+            if !captures_only && start_row & (1 << i) != 0 && occupied_board & (1 << two_steps) == 0 {
+                moveable |= 1 << two_steps;
             }
+        }
 
-            // Knights
-            let mut tmp = board[PieceNum::BlackKnight as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable = constants.knight_reach[i as usize];
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::BlackKnight as usize] -= 1 << i;
-                    new_board[PieceNum::BlackKnight as usize] += 1 << j;
-
-                    new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
+        while moveable != 0 {
+            let j = moveable.trailing_zeros();
+            moveable &= moveable - 1;
 
-                tmp -= 1 << i;
+            let mut mov = Move::new(i, j, PieceType::Pawn);
+            if board.en_passant & (1 << j) != 0 {
+                mov = mov.with_capture(PieceType::Pawn).with_flags(EN_PASSANT);
+            } else if let Some(captured) = board.piece_type_at(opposite_offset, j as usize) {
+                mov = mov.with_capture(captured);
+            } else if i.abs_diff(j) == 16 {
+                mov = mov.with_flags(DOUBLE_PAWN_PUSH);
             }
 
-            // Bishops
-            let mut tmp = board[PieceNum::BlackBishop as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
-
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::BlackBishop as usize] -= 1 << i;
-                    new_board[PieceNum::BlackBishop as usize] += 1 << j;
-
-                    new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
-
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
+            if last_row & (1 << j) != 0 {
+                for typ in PROMOTION_PIECES {
+                    possible_moves.push(mov.with_promotion(typ));
                 }
-
-                tmp -= 1 << i;
+            } else {
+                possible_moves.push(mov);
             }
+        }
+    }
 
-            // Queen
-            let mut tmp = board[PieceNum::BlackQueen as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable_straight = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                let reachable_diagonals = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                let reachable = reachable_straight | reachable_diagonals;
-                let mut moveable = reachable & !own_pieces;
-
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
+    for piece_type in [PieceType::Rook, PieceType::Knight, PieceType::Bishop, PieceType::Queen, PieceType::King] {
+        let mut tmp = board[offset + piece_type as usize];
+        while tmp != 0 {
+            let i = tmp.trailing_zeros();
+            tmp &= tmp - 1;
 
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::BlackQueen as usize] -= 1 << i;
-                    new_board[PieceNum::BlackQueen as usize] += 1 << j;
+            let reachable = match piece_type {
+                PieceType::Rook => constants.rook_reach.get(i as usize, occupied_board),
+                PieceType::Knight => constants.knight_reach[i as usize],
+                PieceType::Bishop => constants.bishop_reach.get(i as usize, occupied_board),
+                PieceType::Queen => constants.rook_reach.get(i as usize, occupied_board) | constants.bishop_reach.get(i as usize, occupied_board),
+                _ => constants.king_reach[i as usize]
+            };
 
-                    new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
+            let mut moveable = match captures_only {
+                true => reachable & opposite_pieces,
+                false => reachable & !own_pieces
+            };
+            while moveable != 0 {
+                let j = moveable.trailing_zeros();
+                moveable &= moveable - 1;
 
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
+                let mov = Move::new(i, j, piece_type);
+                match opposite_pieces & (1 << j) != 0 {
+                    true => possible_moves.push(mov.with_capture(board.piece_type_at(opposite_offset, j as usize).unwrap())),
+                    false => possible_moves.push(mov)
                 }
-
-                tmp -= 1 << i;
             }
+        }
+    }
 
-            // King
-            let mut tmp = board[PieceNum::BlackKing as usize];
-            while tmp != 0 {
-                let i = tmp.trailing_zeros();
-
-                let reachable = constants.king_reach[i as usize];
-                let mut moveable = reachable & !own_pieces;
+    if !captures_only {
+        generate_castling_moves(board, occupied_board, constants, &mut possible_moves);
+    }
 
-                while moveable != 0 {
-                    let j = moveable.trailing_zeros();
+    possible_moves
+}
 
-                    let mut new_board = board.clone();
-                    new_board[PieceNum::BlackKing as usize] -= 1 << i;
-                    new_board[PieceNum::BlackKing as usize] += 1 << j;
+// (rights, king from, king to, rook from, squares that must be empty, squares that must not be attacked)
+type CastlingMove = (u8, u32, u32, u32, u64, u64);
 
-                    new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                    new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                    new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
+// King side first, then queen side
+const CASTLING_MOVES: [[CastlingMove; 2]; 2] = [
+    [
+        (WHITE_KING_SIDE_CASTLE, 4, 6, 7, (1 << 5) | (1 << 6), (1 << 4) | (1 << 5) | (1 << 6)),
+        (WHITE_QUEEN_SIDE_CASTLE, 4, 2, 0, (1 << 1) | (1 << 2) | (1 << 3), (1 << 2) | (1 << 3) | (1 << 4))
+    ],
+    [
+        (BLACK_KING_SIDE_CASTLE, 60, 62, 63, (1 << 61) | (1 << 62), (1 << 60) | (1 << 61) | (1 << 62)),
+        (BLACK_QUEEN_SIDE_CASTLE, 60, 58, 56, (1 << 57) | (1 << 58) | (1 << 59), (1 << 58) | (1 << 59) | (1 << 60))
+    ]
+];
 
-                    possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                    moveable -= 1 << j;
-                }
+fn generate_castling_moves(board: &BitBoard, occupied_board: u64, constants: &Constants, possible_moves: &mut Vec<Move>) {
+    let color = board.turn;
+    let king = color as usize * 6 + PieceType::King as usize;
+    let rook = color as usize * 6 + PieceType::Rook as usize;
 
-                tmp -= 1 << i;
-            }
+    for (rights, king_from, king_to, rook_from, empty, safe) in CASTLING_MOVES[color as usize] {
+        if board.castling_rights & rights == 0 {
+            continue;
         }
-    }
 
-    // Validate
-    let mut res = Vec::new();
-    for mov in possible_moves {
-        let b = mov.1;
-
-        if !is_in_check(&b, color, constants) {
-            res.push(mov);
+        if board[king] & (1 << king_from) == 0 || board[rook] & (1 << rook_from) == 0 || occupied_board & empty != 0 {
+            continue;
         }
-    }
 
-    res
-}
+        let mut squares = safe;
+        let mut attacked = false;
+        while squares != 0 && !attacked {
+            attacked = is_square_attacked(board, squares.trailing_zeros() as usize, color.opposite_color(), constants);
+            squares &= squares - 1;
+        }
 
-fn piece_to_char(piece: &ChessPiece) -> char {
-    match piece.color {
-        PieceColor::White => match piece.typ {
-                                PieceType::Pawn => 'p',
-                                PieceType::Rook => 'r',
-                                PieceType::Knight => 'n',
-                                PieceType::Bishop => 'b',
-                                PieceType::Queen => 'q',
-                                PieceType::King => 'k'
-                            }
-        PieceColor::Black => match piece.typ {
-                                PieceType::Pawn => 'P',
-                                PieceType::Rook => 'R',
-                                PieceType::Knight => 'N',
-                                PieceType::Bishop => 'B',
-                                PieceType::Queen => 'Q',
-                                PieceType::King => 'K'
-                            }
+        if !attacked {
+            possible_moves.push(Move::new(king_from, king_to, PieceType::King).with_flags(CASTLING));
+        }
     }
-
-    
 }
 
 fn get_piece_str(board: &BitBoard, letter: u64, number: u64) -> &str {
@@ -1225,7 +514,7 @@ pub fn get_bitboard_ascii(board: &BitBoard, use_unicode: bool) -> String {
 }
 
 pub fn board_to_bitboard(board: &NormalBoard) -> BitBoard{
-    let mut res = [0; 12];
+    let mut res = BitBoard::new_empty_board();
     for i in 0..8 {
         for j in 0..8 {
             if let Ok(Some(piece)) = board.get_piece(i, j) {
@@ -1279,436 +568,120 @@ pub fn board_to_bitboard(board: &NormalBoard) -> BitBoard{
         }
     }
 
+    res.castling_rights = get_castling_rights(board);
+    res.turn = board.get_turn();
+    res.en_passant = match board.get_en_passant() {
+        Some((letter, number)) => 1 << pos_to_num(letter as u64, number as u64),
+        None => 0
+    };
+    res.halfmove_clock = board.get_halfmove_clock();
+    res.fullmove_number = board.get_fullmove_number();
+    res.hash = res.compute_hash();
+
     res
 }
 
-pub fn bitboard_check_game_end(bb: &BitBoard, prev_board: Option<&BitBoard>, turn: PieceColor, constants: &Constants) -> EndType {
-    let opponent_color = turn.opposite_color();
-    let possible_moves = generate_possible_moves(bb, prev_board, turn, constants);
+fn get_castling_rights(board: &NormalBoard) -> u8 {
+    let unmoved = |letter: i32, number: i32, typ: PieceType, color: PieceColor| -> bool {
+        match board.get_piece(letter, number) {
+            Ok(Some(piece)) => piece.typ == typ && piece.color == color && !piece.has_moved(),
+            _ => false
+        }
+    };
 
-    if possible_moves.len() == 0 {
-        let opponent_reach = get_reach_board(&bb, opponent_color, constants);
+    let mut rights = 0;
 
-        let checkmate = match turn {
-            PieceColor::White => opponent_reach & bb[5] == bb[5],
-            PieceColor::Black => opponent_reach & bb[11] == bb[11]
-        };
+    if unmoved(4, 0, PieceType::King, PieceColor::White) {
+        if unmoved(7, 0, PieceType::Rook, PieceColor::White) {
+            rights |= WHITE_KING_SIDE_CASTLE;
+        }
+        if unmoved(0, 0, PieceType::Rook, PieceColor::White) {
+            rights |= WHITE_QUEEN_SIDE_CASTLE;
+        }
+    }
 
-        if checkmate {
-            return EndType::Checkmate(turn);
-        } else {
-            return EndType::Tie;
+    if unmoved(4, 7, PieceType::King, PieceColor::Black) {
+        if unmoved(7, 7, PieceType::Rook, PieceColor::Black) {
+            rights |= BLACK_KING_SIDE_CASTLE;
+        }
+        if unmoved(0, 7, PieceType::Rook, PieceColor::Black) {
+            rights |= BLACK_QUEEN_SIDE_CASTLE;
         }
     }
 
-    EndType::NoEnd
+    rights
 }
 
-impl ChessBoardContract for BitBoard {
-    fn generate_moves(&self, prev_board: Option<&BitBoard>, turn: PieceColor, constants: &Constants) -> Result<Vec<(String, BitBoard)>, ChessError> {
-        let mut possible_moves = Vec::new();
-
-        let board = self;
-        let opposite_color = turn.opposite_color();
-        let occupied_board = get_occupied_board(board);
-        let own_pieces = get_full_color_board(board, turn);
-        let opposite_pieces = get_full_color_board(board, opposite_color);
-        let reach_board = get_reach_board(board, turn, constants);
-        let opposite_reach_board = get_reach_board(board, opposite_color, constants);
-
-        match turn {
-            PieceColor::White => {
-                // Pawns
-                let mut tmp = board[PieceNum::WhitePawn as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    if occupied_board & 1 << (i + 8) == 0 {
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhitePawn as usize] -= 1 << i;
-                        if 55 <= i + 8 && i + 8 < 64 {
-                            new_board[PieceNum::WhiteQueen as usize] += 1 << (i + 8);
-                        } else {
-                            new_board[PieceNum::WhitePawn as usize] += 1 << (i + 8);
-                        }
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 + 8)), new_board));
-
-                        if 7 <= i && i < 16 && occupied_board & 1 << (i + 16) == 0 {
-                            let mut new_board = board.clone();
-                            new_board[PieceNum::WhitePawn as usize] -= 1 << i;
-                            new_board[PieceNum::WhitePawn as usize] += 1 << (i + 16);
-                            possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 + 16)), new_board));
-                        }
-                    }
-
-                    let mut possible_attacks = constants.pawn_reach[0][i as usize] & opposite_pieces;
-                    while possible_attacks != 0 {
-                        let j = possible_attacks.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhitePawn as usize] -= 1 << i;
-                        new_board[PieceNum::WhitePawn as usize] += 1 << j;
-
-                        new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        possible_attacks -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Rooks
-                let mut tmp = board[PieceNum::WhiteRook as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhiteRook as usize] -= 1 << i;
-                        new_board[PieceNum::WhiteRook as usize] += 1 << j;
-
-                        new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Knights
-                let mut tmp = board[PieceNum::WhiteKnight as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable = constants.knight_reach[i as usize];
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhiteKnight as usize] -= 1 << i;
-                        new_board[PieceNum::WhiteKnight as usize] += 1 << j;
-
-                        new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Bishops
-                let mut tmp = board[PieceNum::WhiteBishop as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhiteBishop as usize] -= 1 << i;
-                        new_board[PieceNum::WhiteBishop as usize] += 1 << j;
-
-                        new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Queen
-                let mut tmp = board[PieceNum::WhiteQueen as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable_straight = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                    let reachable_diagonals = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                    let reachable = reachable_straight | reachable_diagonals;
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhiteQueen as usize] -= 1 << i;
-                        new_board[PieceNum::WhiteQueen as usize] += 1 << j;
-
-                        new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // King
-                let mut tmp = board[PieceNum::WhiteKing as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable = constants.king_reach[i as usize];
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::WhiteKing as usize] -= 1 << i;
-                        new_board[PieceNum::WhiteKing as usize] += 1 << j;
-
-                        new_board[PieceNum::BlackPawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::BlackRook as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::BlackQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-            },
-            PieceColor::Black => {
-                // Pawns
-                let mut tmp = board[PieceNum::BlackPawn as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    if occupied_board & 1 << (i - 8) == 0 {
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackPawn as usize] -= 1 << i;
-                        if 0 <= i - 8 && i - 8 < 8 {
-                            new_board[PieceNum::BlackQueen as usize] += 1 << (i - 8);
-                        } else {
-                            new_board[PieceNum::BlackPawn as usize] += 1 << (i - 8);
-                        }
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 - 8)), new_board));
+pub fn bitboard_check_game_end(bb: &BitBoard, constants: &Constants) -> EndType {
+    let turn = bb.turn;
 
-                        if 48 <= i && i < 56 && occupied_board & 1 << (i - 16) == 0 {
-                            let mut new_board = board.clone();
-                            new_board[PieceNum::BlackPawn as usize] -= 1 << i;
-                            new_board[PieceNum::BlackPawn as usize] += 1 << (i - 16);
-                            possible_moves.push(((num_to_pos(i as u64), num_to_pos(i as u64 - 16)), new_board));
-                        }
-                    }
-
-                    let mut possible_attacks = constants.pawn_reach[1][i as usize] & opposite_pieces;
-                    while possible_attacks != 0 {
-                        let j = possible_attacks.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackPawn as usize] -= 1 << i;
-                        new_board[PieceNum::BlackPawn as usize] += 1 << j;
-
-                        new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        possible_attacks -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Rooks
-                let mut tmp = board[PieceNum::BlackRook as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackRook as usize] -= 1 << i;
-                        new_board[PieceNum::BlackRook as usize] += 1 << j;
-
-                        new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Knights
-                let mut tmp = board[PieceNum::BlackKnight as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable = constants.knight_reach[i as usize];
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackKnight as usize] -= 1 << i;
-                        new_board[PieceNum::BlackKnight as usize] += 1 << j;
-
-                        new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Bishops
-                let mut tmp = board[PieceNum::BlackBishop as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                    let mut moveable = reachable & !own_pieces;
-
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
-
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackBishop as usize] -= 1 << i;
-                        new_board[PieceNum::BlackBishop as usize] += 1 << j;
-
-                        new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
-
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
-
-                    tmp -= 1 << i;
-                }
-
-                // Queen
-                let mut tmp = board[PieceNum::BlackQueen as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
-
-                    let reachable_straight = constants.rook_reach[i as usize].get(&(constants.row_and_column_mask[i as usize] & occupied_board)).unwrap();
-                    let reachable_diagonals = constants.bishop_reach[i as usize].get(&(constants.diagonal_mask[i as usize] & occupied_board)).unwrap();
-                    let reachable = reachable_straight | reachable_diagonals;
-                    let mut moveable = reachable & !own_pieces;
+    if generate_legal_moves(bb, constants).is_empty() {
+        match is_in_check(bb, turn, constants) {
+            true => return EndType::Checkmate(turn.opposite_color()),
+            false => return EndType::Tie(TieType::Stalemate)
+        }
+    }
 
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
+    if bb.halfmove_clock >= FIFTY_MOVE_RULE_PLIES {
+        return EndType::Tie(TieType::FiftyMoveRule);
+    }
 
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackQueen as usize] -= 1 << i;
-                        new_board[PieceNum::BlackQueen as usize] += 1 << j;
+    if bb.has_insufficient_material() {
+        return EndType::Tie(TieType::InsufficientMaterial);
+    }
 
-                        new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
+    EndType::NoEnd
+}
 
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
+impl ChessBoardContract for BitBoard {
+    type Undo = MoveUndo;
 
-                    tmp -= 1 << i;
-                }
+    fn generate_moves(&self, constants: &Constants) -> Result<Vec<Move>, ChessError> {
+        Ok(generate_legal_moves(self, constants))
+    }
 
-                // King
-                let mut tmp = board[PieceNum::BlackKing as usize];
-                while tmp != 0 {
-                    let i = tmp.trailing_zeros();
+    fn generate_captures(&self, constants: &Constants) -> Result<Vec<Move>, ChessError> {
+        Ok(generate_legal_captures(self, constants))
+    }
 
-                    let reachable = constants.king_reach[i as usize];
-                    let mut moveable = reachable & !own_pieces;
+    fn in_check(&self, constants: &Constants) -> bool {
+        is_in_check(self, self.turn, constants)
+    }
 
-                    while moveable != 0 {
-                        let j = moveable.trailing_zeros();
+    fn make_move(&mut self, mov: Move) -> Result<MoveUndo, ChessError> {
+        Ok(Position::make_move(self, mov))
+    }
 
-                        let mut new_board = board.clone();
-                        new_board[PieceNum::BlackKing as usize] -= 1 << i;
-                        new_board[PieceNum::BlackKing as usize] += 1 << j;
+    fn unmake_move(&mut self, mov: Move, undo: MoveUndo) {
+        Position::unmake_move(self, mov, undo)
+    }
 
-                        new_board[PieceNum::WhitePawn as usize] &=  !(1 << j);
-                        new_board[PieceNum::WhiteRook as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteKnight as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteBishop as usize] &= !(1 << j);
-                        new_board[PieceNum::WhiteQueen as usize] &= !(1 << j);
+    fn make_null_move(&mut self) -> MoveUndo {
+        Position::make_null_move(self)
+    }
 
-                        possible_moves.push(((num_to_pos(i as u64), num_to_pos(j as u64)), new_board));
-                        moveable -= 1 << j;
-                    }
+    fn unmake_null_move(&mut self, undo: MoveUndo) {
+        Position::unmake_null_move(self, undo)
+    }
 
-                    tmp -= 1 << i;
-                }
-            }
-        }
+    fn check_game_end(&self, constants: &Constants) -> Result<EndType, ChessError> {
+        Ok(bitboard_check_game_end(self, constants))
+    }
 
-        // Validate
-        let mut res: Vec<(String, BitBoard)> = Vec::new();
-        for mov in possible_moves {
-            let (mov_pos, board) = mov;
+    fn get_turn(&self) -> PieceColor {
+        self.turn
+    }
 
-            if !is_in_check(&board, turn, constants) {
-                let mov_str = format!("{}{} {}{}", get_letter(mov_pos.0.0 as usize), get_number(mov_pos.0.1 as usize), get_letter(mov_pos.1.0 as usize), get_number(mov_pos.1.1 as usize));
-                res.push((mov_str, board));
-            }
-        }
+    fn get_halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
 
-        Ok(res)
+    fn get_fullmove_number(&self) -> u32 {
+        self.fullmove_number
     }
 
-    fn check_game_end(&self, prev_board: Option<&Self>, turn: PieceColor, constants: &Constants) -> Result<EndType, ChessError> {
-        Ok(bitboard_check_game_end(self, prev_board, turn, constants))
+    fn get_hash(&self) -> u64 {
+        self.hash
     }
 
     fn get_value_of_pieces(&self, piece_values: [i32; 6]) -> i32 {
@@ -1731,10 +704,49 @@ impl ChessBoardContract for BitBoard {
         res
     }
 
+    fn get_value_of_placements(&self, placement_scores: &[[[i32; 8]; 8]; 6]) -> i32 {
+        let mut res = 0;
+
+        for (typ, scores) in placement_scores.iter().enumerate() {
+            let mut white_pieces = self[typ];
+            while white_pieces != 0 {
+                let square = white_pieces.trailing_zeros() as usize;
+                res += scores[square & 7][square >> 3];
+                white_pieces &= white_pieces - 1;
+            }
+
+            let mut black_pieces = self[6 + typ];
+            while black_pieces != 0 {
+                let square = black_pieces.trailing_zeros() as usize;
+                res -= scores[square & 7][7 - (square >> 3)];
+                black_pieces &= black_pieces - 1;
+            }
+        }
+
+        res
+    }
+
+    fn get_piece_count(&self, typ: PieceType) -> u32 {
+        (self[typ as usize] | self[6 + typ as usize]).count_ones()
+    }
+
+    fn has_non_pawn_material(&self, color: PieceColor) -> bool {
+        [PieceType::Rook, PieceType::Knight, PieceType::Bishop, PieceType::Queen].iter()
+            .any(|typ| self[color as usize * 6 + *typ as usize] != 0)
+    }
+
     fn new_board() -> Self {
         generate_start_board()
     }
 
+    fn from_fen(fen: &str) -> Result<Self, ChessError> {
+        Position::from_fen(fen)
+    }
+
+    fn to_fen(&self) -> String {
+        Position::to_fen(*self)
+    }
+
     fn board_ascii(&self, use_unicode: bool) -> String {
         get_bitboard_ascii(self, use_unicode)
     }
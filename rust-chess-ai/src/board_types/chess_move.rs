@@ -0,0 +1,155 @@
+use crate::enums::piece_type::{PieceType, PIECE_TYPES};
+
+use crate::functions::{
+    get_letter,
+    get_number,
+    normalize_move_string
+};
+
+const SQUARE_MASK: u32 = 0x3F;
+const PIECE_MASK: u32 = 0x7;
+const TO_SHIFT: u32 = 6;
+const PIECE_SHIFT: u32 = 12;
+const CAPTURED_SHIFT: u32 = 15;
+const PROMOTION_SHIFT: u32 = 18;
+const FLAGS_SHIFT: u32 = 21;
+
+pub const CAPTURE: u32 = 1;
+pub const DOUBLE_PAWN_PUSH: u32 = 2;
+pub const EN_PASSANT: u32 = 4;
+pub const CASTLING: u32 = 8;
+
+// A move packed into 32 bits, with everything needed to make and take back the move without
+// looking at the board: bits 0-5 the square it starts on, 6-11 the square it ends on, 12-14 the
+// piece that moves, 15-17 the piece it captures, 18-20 the piece a pawn is promoted to (0 if
+// there is none, as a pawn can not be promoted to a pawn) and 21-24 the flags above
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move(u32);
+
+impl Move {
+    pub fn new(from: u32, to: u32, piece: PieceType) -> Self {
+        Self(from | (to << TO_SHIFT) | ((piece as u32) << PIECE_SHIFT))
+    }
+
+    pub fn with_capture(self, captured: PieceType) -> Self {
+        Self(self.0 | ((captured as u32) << CAPTURED_SHIFT) | (CAPTURE << FLAGS_SHIFT))
+    }
+
+    pub fn with_promotion(self, promotion: PieceType) -> Self {
+        Self(self.0 | ((promotion as u32) << PROMOTION_SHIFT))
+    }
+
+    pub fn with_flags(self, flags: u32) -> Self {
+        Self(self.0 | (flags << FLAGS_SHIFT))
+    }
+
+    pub fn source_square(self) -> u32 {
+        self.0 & SQUARE_MASK
+    }
+
+    pub fn target_square(self) -> u32 {
+        (self.0 >> TO_SHIFT) & SQUARE_MASK
+    }
+
+    pub fn piece(self) -> PieceType {
+        PIECE_TYPES[((self.0 >> PIECE_SHIFT) & PIECE_MASK) as usize]
+    }
+
+    pub fn captured(self) -> Option<PieceType> {
+        match self.is_capture() {
+            true => Some(PIECE_TYPES[((self.0 >> CAPTURED_SHIFT) & PIECE_MASK) as usize]),
+            false => None
+        }
+    }
+
+    pub fn promotion(self) -> Option<PieceType> {
+        match (self.0 >> PROMOTION_SHIFT) & PIECE_MASK {
+            0 => None,
+            typ => Some(PIECE_TYPES[typ as usize])
+        }
+    }
+
+    pub fn is_capture(self) -> bool {
+        self.has_flag(CAPTURE)
+    }
+
+    pub fn is_double_pawn_push(self) -> bool {
+        self.has_flag(DOUBLE_PAWN_PUSH)
+    }
+
+    pub fn is_en_passant(self) -> bool {
+        self.has_flag(EN_PASSANT)
+    }
+
+    pub fn is_castling(self) -> bool {
+        self.has_flag(CASTLING)
+    }
+
+    fn has_flag(self, flag: u32) -> bool {
+        (self.0 >> FLAGS_SHIFT) & flag != 0
+    }
+
+    // The squares and the promotion in 15 bits, which is enough to tell the moves of one position apart
+    pub fn key(self) -> u16 {
+        let promotion = self.promotion().map(|typ| typ as u32).unwrap_or(0);
+        (self.source_square() | (self.target_square() << TO_SHIFT) | (promotion << PIECE_SHIFT)) as u16
+    }
+
+    // "e2e4", or "e7e8q" for a promotion
+    pub fn to_uci(self) -> String {
+        let mut res = square_name(self.source_square());
+        res.push_str(&square_name(self.target_square()));
+
+        if let Some(typ) = self.promotion() {
+            res.push(typ.get_char());
+        }
+
+        res
+    }
+
+    // "e2-e4", "Ng1-f3", "e5xd6", "e7-e8=Q" or "O-O"
+    pub fn to_long_algebraic(self) -> String {
+        if self.is_castling() {
+            return castling_notation(self).to_string();
+        }
+
+        let mut res = String::with_capacity(8);
+        if self.piece() != PieceType::Pawn {
+            res.push(self.piece().get_char().to_ascii_uppercase());
+        }
+
+        res.push_str(&square_name(self.source_square()));
+        res.push(if self.is_capture() { 'x' } else { '-' });
+        res.push_str(&square_name(self.target_square()));
+
+        if let Some(typ) = self.promotion() {
+            res.push('=');
+            res.push(typ.get_char().to_ascii_uppercase());
+        }
+
+        res
+    }
+}
+
+pub fn square_name(square: u32) -> String {
+    let square = square as usize;
+    [get_letter(square & 7), get_number(square >> 3)].iter().collect()
+}
+
+pub fn castling_notation(mov: Move) -> &'static str {
+    match mov.target_square() > mov.source_square() {
+        true => "O-O",
+        false => "O-O-O"
+    }
+}
+
+// The squares and promotion of a move written in one of the formats normalize_move_string accepts
+pub fn parse_coordinates(move_str: &str) -> Option<(u32, u32, Option<PieceType>)> {
+    let characters: Vec<char> = normalize_move_string(move_str)?.chars().collect();
+
+    let from = (characters[0] as u32 - 'a' as u32) + ((characters[1] as u32 - '1' as u32) << 3);
+    let to = (characters[3] as u32 - 'a' as u32) + ((characters[4] as u32 - '1' as u32) << 3);
+    let promotion = characters.get(5).and_then(|c| PieceType::from_char(*c));
+
+    Some((from, to, promotion))
+}
@@ -0,0 +1,148 @@
+// Sliding piece attacks with magic bitboards. The blockers on the rays of a square are
+// multiplied by a magic number, and the top bits of the product index a table with the
+// attacks for that blocker configuration. The magics were found by trying random sparse
+// numbers until one mapped every configuration without conflicts
+
+pub const ROOK_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+pub const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+const ROOK_MAGICS: [u64; 64] = [
+    0x0980008011400020, 0x8340004410002000, 0x0880200090008268, 0x0080080080100004,
+    0x8100110004020800, 0x0300010004000822, 0x08801A0029000080, 0x8100050001204882,
+    0x0844800081400320, 0x0804402010004000, 0x0108802003100480, 0x0004808008001000,
+    0x0003001801001014, 0x0002000200041008, 0x0004008108042210, 0x0105000100009042,
+    0x0400808000400021, 0xC100404010002000, 0x0060008010002088, 0x0400808008001000,
+    0x4440808008000400, 0x1002008004000280, 0x40024400300D1248, 0x0010020000408104,
+    0x0101008200204200, 0x8020002040005000, 0x4100100080802000, 0x4008006A80100280,
+    0x1020080080040080, 0x0004010040020040, 0x0018A12400080290, 0x6140004200008104,
+    0x4000400020800090, 0x2020002080804000, 0x0000408202002010, 0x0080100501000820,
+    0x0000800400800800, 0x000A200408014010, 0x0100800200800100, 0xA00800570200008C,
+    0x008000406000C010, 0x1040100028002000, 0x0048200100110040, 0x0068490210030020,
+    0x1009080005010010, 0x2142000804010100, 0x1001080110840002, 0x1801004400820001,
+    0x010440208D020200, 0x0000400020008080, 0x0200200080100280, 0x0000100020090100,
+    0x0204008008020480, 0x8104010040020040, 0x78000201B0080400, 0x0040800051002880,
+    0x0050108001002041, 0x208A801100614003, 0x0006002042089082, 0x0011090004201001,
+    0x1002001004200802, 0x0005000208040001, 0x0002002701AC0822, 0x000010250184004A,
+];
+const BISHOP_MAGICS: [u64; 64] = [
+    0xC0A0012206040EA0, 0x8010228200420001, 0x0110008220400400, 0x02445C0080106000,
+    0x0044042004008100, 0x0880900420408C05, 0x0201080110080002, 0x0000108094202000,
+    0x0000042002040108, 0x0000623024110042, 0x0086100094811002, 0x0000044502002080,
+    0x0100460211400040, 0x0008109004200004, 0x0202320084844000, 0x8040042421041009,
+    0x201010C05102008C, 0x1020888208024080, 0x0108000C80290200, 0x8048000420425203,
+    0x0005000090402000, 0x2080400201104100, 0x8820420111101000, 0x4AC0302208821802,
+    0x000440001002A840, 0x2002200010041080, 0x1012080201004400, 0x8440040002410120,
+    0x1090820084010400, 0x2084852012021000, 0x12040062C1011003, 0x02008205E1090080,
+    0x088C102808042080, 0x0802102200904280, 0x8020209002080020, 0x2200080800060A00,
+    0x20C0004010010100, 0x0802004100821003, 0x0008024400008080, 0x0000840102008090,
+    0x0030A40420244007, 0x0A19084210011282, 0x0004082090019806, 0x6108004208020080,
+    0x0081200410110100, 0x1040810701010208, 0x0282047832012080, 0x0010020099000020,
+    0x000E010422400840, 0x10204208B0089090, 0x081004440C048000, 0x88C0180084040001,
+    0x3100020803040080, 0x890070A041210C00, 0x0020200101010A09, 0x0004100240410400,
+    0x0006004402080200, 0x0801062484042000, 0x00010002D7441004, 0x0810080000208800,
+    0x0000020808030411, 0x1450001020014440, 0x004060081081A288, 0x0044011404108A00,
+];
+
+#[derive(Clone)]
+pub struct MagicTable {
+    masks: [u64; 64],
+    magics: [u64; 64],
+    shifts: [u32; 64],
+    offsets: [usize; 64],
+    attacks: Vec<u64>
+}
+
+impl MagicTable {
+    pub fn rook() -> Self {
+        Self::new(&ROOK_DIRECTIONS, &ROOK_MAGICS)
+    }
+
+    pub fn bishop() -> Self {
+        Self::new(&BISHOP_DIRECTIONS, &BISHOP_MAGICS)
+    }
+
+    fn new(directions: &[(i32, i32); 4], magics: &[u64; 64]) -> Self {
+        let mut masks = [0; 64];
+        let mut shifts = [0; 64];
+        let mut offsets = [0; 64];
+        let mut attacks = Vec::new();
+
+        for i in 0..64 {
+            let mask = relevant_blockers(i, directions);
+            let bits = mask.count_ones();
+            masks[i] = mask;
+            shifts[i] = 64 - bits;
+            offsets[i] = attacks.len();
+            attacks.resize(attacks.len() + (1 << bits), 0);
+
+            // Goes through every subset of the mask
+            let mut blockers: u64 = 0;
+            loop {
+                let index = offsets[i] + (blockers.wrapping_mul(magics[i]) >> shifts[i]) as usize;
+                let reach = slider_reach(i, blockers, directions);
+                assert!(attacks[index] == 0 || attacks[index] == reach, "Magic number for square {} has conflicts", i);
+                attacks[index] = reach;
+
+                blockers = blockers.wrapping_sub(mask) & mask;
+                if blockers == 0 {
+                    break;
+                }
+            }
+        }
+
+        Self {
+            masks,
+            magics: *magics,
+            shifts,
+            offsets,
+            attacks
+        }
+    }
+
+    // The squares reached from square i, including the first occupied square in every direction
+    #[inline]
+    pub fn get(&self, i: usize, occupied_board: u64) -> u64 {
+        let blockers = occupied_board & self.masks[i];
+        self.attacks[self.offsets[i] + (blockers.wrapping_mul(self.magics[i]) >> self.shifts[i]) as usize]
+    }
+}
+
+// Pieces on the last square of a ray do not block anything, so they are left out of the mask
+fn relevant_blockers(i: usize, directions: &[(i32, i32); 4]) -> u64 {
+    let (letter, number) = ((i % 8) as i32, (i / 8) as i32);
+    let mut mask = 0;
+
+    for (letter_const, number_const) in directions {
+        let mut new_letter = letter + letter_const;
+        let mut new_number = number + number_const;
+        while (0..8).contains(&(new_letter + letter_const)) && (0..8).contains(&(new_number + number_const)) {
+            mask |= 1 << (new_letter + new_number * 8);
+            new_letter += letter_const;
+            new_number += number_const;
+        }
+    }
+
+    mask
+}
+
+// Walks every ray until it leaves the board or hits an occupied square
+pub fn slider_reach(i: usize, occupied_board: u64, directions: &[(i32, i32); 4]) -> u64 {
+    let (letter, number) = ((i % 8) as i32, (i / 8) as i32);
+    let mut reach = 0;
+
+    for (letter_const, number_const) in directions {
+        let mut new_letter = letter + letter_const;
+        let mut new_number = number + number_const;
+        while (0..8).contains(&new_letter) && (0..8).contains(&new_number) {
+            let pos = 1 << (new_letter + new_number * 8);
+            reach |= pos;
+            if occupied_board & pos != 0 {
+                break;
+            }
+            new_letter += letter_const;
+            new_number += number_const;
+        }
+    }
+
+    reach
+}
@@ -1,7 +1,8 @@
 use crate::enums::{
     piece_color::PieceColor,
     end_type::EndType,
-    piece_type::PieceType,
+    tie_type::TieType,
+    piece_type::{PieceType, PIECE_TYPES, PROMOTION_PIECES},
     chess_error::ChessError
 };
 
@@ -14,7 +15,20 @@ use crate::functions::{
     get_number
 };
 
-use crate::board_types::bitboard::Constants;
+use crate::board_types::bitboard::{
+    Constants,
+    BitBoard,
+    board_to_bitboard
+};
+use crate::board_types::position::{
+    Position,
+    WHITE_KING_SIDE_CASTLE,
+    WHITE_QUEEN_SIDE_CASTLE,
+    BLACK_KING_SIDE_CASTLE,
+    BLACK_QUEEN_SIDE_CASTLE,
+    FIFTY_MOVE_RULE_PLIES
+};
+use crate::board_types::chess_move::{Move, parse_coordinates, DOUBLE_PAWN_PUSH, EN_PASSANT, CASTLING};
 use std::sync::Arc;
 
 
@@ -30,7 +44,7 @@ pub struct ChessPiece {
 }
 
 impl ChessPiece {
-    fn new(typ: PieceType, color: PieceColor) -> Self {
+    pub fn new(typ: PieceType, color: PieceColor) -> Self {
         Self {
             typ,
             color,
@@ -38,6 +52,10 @@ impl ChessPiece {
         }
     }
 
+    pub fn has_moved(&self) -> bool {
+        self.moved
+    }
+
     // Maybe this and the next function should just use self?
     fn piece_to_char(piece: &ChessPiece) -> char {
         match piece.color {
@@ -123,7 +141,14 @@ impl<'a> Iterator for NormalBoardIter<'a> {
 // Letter is first index then number
 #[derive(Debug, Clone)]
 pub struct NormalBoard {
-    board: [[Option<ChessPiece>; 8]; 8]
+    board: [[Option<ChessPiece>; 8]; 8],
+    turn: PieceColor,
+    // The position a pawn skipped over with a double step on the previous move
+    en_passant: Option<Pos>,
+    // Moves made since the last capture or pawn move
+    halfmove_clock: u32,
+    // Starts at 1 and is incremented after every move by black
+    fullmove_number: u32
 }
 
 impl NormalBoard {
@@ -164,6 +189,12 @@ impl NormalBoard {
             Some(piece) => {
                 let mut piece = piece.clone();
                 piece.moved = true;
+
+                // Pawn moves and captures reset the clock
+                if piece.typ == PieceType::Pawn || self.get_piece(to_letter, to_number)?.is_some() {
+                    self.halfmove_clock = 0;
+                }
+
                 self.set_piece(to_letter, to_number, Some(&piece))?;
                 self.delete_piece(from_letter, from_number)?;
             }
@@ -195,8 +226,31 @@ impl NormalBoard {
 
     pub fn new_empty_board() -> Self {
         Self {
-            board: Self::blank_board()
+            board: Self::blank_board(),
+            turn: PieceColor::White,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1
+        }
+    }
+
+    pub fn get_en_passant(&self) -> Option<Pos> {
+        self.en_passant
+    }
+
+    // The state every generated move starts from: the other player's turn, no en passant
+    // position and the clocks advanced as if the move was not a capture or pawn move
+    fn next_board(&self) -> NormalBoard {
+        let mut res = self.clone();
+
+        res.turn = self.turn.opposite_color();
+        res.en_passant = None;
+        res.halfmove_clock += 1;
+        if self.turn == PieceColor::Black {
+            res.fullmove_number += 1;
         }
+
+        res
     }
 
     pub fn new_start_board() -> Self {
@@ -229,7 +283,11 @@ impl NormalBoard {
         board[7][7] = Some(ChessPiece::new(PieceType::Rook, PieceColor::Black));
 
         Self {
-            board: board
+            board: board,
+            turn: PieceColor::White,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1
         }
     }
 
@@ -376,6 +434,8 @@ impl NormalBoard {
                         break;
                     }
                 }
+
+                reach_board[new_letter as usize][new_number as usize] = true;
             }
         }
     }
@@ -419,10 +479,11 @@ impl NormalBoard {
         }
     }
 
-    pub fn generate_possible_moves(&self, prev_board: Option<&NormalBoard>, turn: PieceColor) -> Result<Vec<(String, NormalBoard)>, ChessError> {
+    pub fn generate_possible_moves(&self) -> Result<Vec<(String, NormalBoard)>, ChessError> {
+        let turn = self.turn;
         let res = self.iter()
             .filter(|(_, piece)| piece.color == turn)
-            .map(|(pos, piece)| self.generate_possible_moves_for_piece(piece, prev_board, turn, pos))
+            .map(|(pos, piece)| self.generate_possible_moves_for_piece(piece, turn, pos))
             .fold(Ok(Vec::with_capacity(50)), |acc, item| {
                 let mut acc_val: Vec<(String, NormalBoard)> = acc?;
                 let mut item_val = item?;
@@ -434,9 +495,9 @@ impl NormalBoard {
         Ok(res?)
     }
 
-    fn generate_possible_moves_for_piece(&self, piece: &ChessPiece, prev_board: Option<&NormalBoard>, turn: PieceColor, pos: Pos) -> Result<Vec<(String, NormalBoard)>, ChessError> {
+    fn generate_possible_moves_for_piece(&self, piece: &ChessPiece, turn: PieceColor, pos: Pos) -> Result<Vec<(String, NormalBoard)>, ChessError> {
         Ok(match piece.typ {
-            PieceType::Pawn => self.generate_possible_pawn_moves(piece, prev_board, pos),
+            PieceType::Pawn => self.generate_possible_pawn_moves(piece, pos),
             PieceType::Rook => self.generate_possible_rook_moves(piece, pos),
             PieceType::Knight => self.generate_possible_knight_moves(piece, pos),
             PieceType::Bishop => self.generate_possible_bishop_moves(piece, pos),
@@ -455,7 +516,7 @@ impl NormalBoard {
         false
     }
 
-    fn generate_possible_pawn_moves(&self, piece: &ChessPiece, prev_board: Option<&NormalBoard>, pos: Pos) -> Result<Vec<(String, NormalBoard)>, ChessError> {
+    fn generate_possible_pawn_moves(&self, piece: &ChessPiece, pos: Pos) -> Result<Vec<(String, NormalBoard)>, ChessError> {
         let (letter, number) = pos;
         let side_const = piece.color.side_const();
 
@@ -465,11 +526,11 @@ impl NormalBoard {
         let new_letter = letter as i32;
         let new_number = number as i32 + side_const;
         if let Ok(None) = self.get_piece(letter as i32, new_number) {
-            let mut new_board = self.clone();
+            let mut new_board = self.next_board();
             new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
             let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
-            possible_moves.push((mov_str, new_board));
+            Self::push_pawn_move(&mut possible_moves, mov_str, new_board, piece.color, new_letter, new_number)?;
         }
 
         // Double step
@@ -479,8 +540,9 @@ impl NormalBoard {
             if let Ok(None) = self.get_piece(letter as i32, new_number) {
                 let new_number = number as i32 + side_const * 2;
                 if let Ok(None) = self.get_piece(letter as i32, new_number) {
-                    let mut new_board = self.clone();
+                    let mut new_board = self.next_board();
                     new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
+                    new_board.en_passant = Some((letter, (number as i32 + side_const) as usize));
 
                     let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
                     possible_moves.push((mov_str, new_board));
@@ -497,11 +559,11 @@ impl NormalBoard {
             let new_number = number as i32 + side_const;
             if let Ok(Some(other_piece)) = self.get_piece(new_letter, new_number) {
                 if piece.color != other_piece.color {
-                    let mut new_board = self.clone();
+                    let mut new_board = self.next_board();
                     new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
                     let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
-                    possible_moves.push((mov_str, new_board));
+                    Self::push_pawn_move(&mut possible_moves, mov_str, new_board, piece.color, new_letter, new_number)?;
                 }
             }
         }
@@ -509,36 +571,39 @@ impl NormalBoard {
         // En passant
         for dir in direction {
             let new_letter = letter as i32 + dir;
-            let new_number = number as i32;
-            if let Ok(Some(other_piece)) = self.get_piece(new_letter, new_number) {
-                if other_piece.color != piece.color && other_piece.typ == PieceType::Pawn {
-                    let new_letter = letter as i32 + dir;
-                    let new_number = number as i32 + side_const;
-                    if let Ok(None) = self.get_piece(new_letter, new_number) {
-                        let new_letter = letter as i32 + dir;
-                        let new_number = number as i32 + side_const * 2;
-                        if let Ok(None) = self.get_piece(new_letter, new_number) {
-                            if let Some(prev_board) = prev_board {
-                                if let Ok(Some(prev_piece)) = prev_board.get_piece(new_letter, new_number) {
-                                    if prev_piece.color != piece.color && prev_piece.typ == PieceType::Pawn {
-                                        let mut new_board = self.clone();
-                                        new_board.move_piece(letter as i32, number as i32, letter as i32 + dir, number as i32 + side_const)?;
-                                        new_board.delete_piece(letter as i32 + dir, number as i32)?;
-                                        
-                                        let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter((letter as i32 + dir) as usize), get_number((number as i32 + side_const) as usize));
-                                        possible_moves.push((mov_str, new_board));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+            let new_number = number as i32 + side_const;
+            if self.en_passant == Some((new_letter as usize, new_number as usize)) {
+                let mut new_board = self.next_board();
+                new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
+                new_board.delete_piece(new_letter, number as i32)?;
+
+                let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
+                possible_moves.push((mov_str, new_board));
             }
         }
 
         Ok(possible_moves)
     }
 
+    // Pawns that reach the last row are replaced with each of the pieces they can be promoted to
+    fn push_pawn_move(possible_moves: &mut Vec<(String, NormalBoard)>, mov_str: String, new_board: NormalBoard, color: PieceColor, letter: i32, number: i32) -> Result<(), ChessError> {
+        if number != 0 && number != 7 {
+            possible_moves.push((mov_str, new_board));
+            return Ok(());
+        }
+
+        for typ in PROMOTION_PIECES {
+            let mut promoted_board = new_board.clone();
+            let mut promoted_piece = ChessPiece::new(typ, color);
+            promoted_piece.moved = true;
+            promoted_board.set_piece(letter, number, Some(&promoted_piece))?;
+
+            possible_moves.push((format!("{}{}", mov_str, typ.get_char()), promoted_board));
+        }
+
+        Ok(())
+    }
+
     fn generate_possible_rook_moves(&self, piece: &ChessPiece, pos: Pos) -> Result<Vec<(String, NormalBoard)>, ChessError> {
         let (letter, number) = pos;
 
@@ -556,7 +621,7 @@ impl NormalBoard {
                 
                 match self.get_piece(new_letter, new_number) {
                     Ok(None) => {
-                        let mut new_board = self.clone();
+                        let mut new_board = self.next_board();
                         new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
                         let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
@@ -564,7 +629,7 @@ impl NormalBoard {
                     },
                     Ok(Some(other_piece)) => {
                         if piece.color != other_piece.color {
-                            let mut new_board = self.clone();
+                            let mut new_board = self.next_board();
                             new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
                             let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
@@ -597,7 +662,7 @@ impl NormalBoard {
 
             match self.get_piece(new_letter, new_number) {
                 Ok(None) => {
-                    let mut new_board = self.clone();
+                    let mut new_board = self.next_board();
                     new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
                     let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
@@ -605,7 +670,7 @@ impl NormalBoard {
                 },
                 Ok(Some(other_piece)) => {
                     if piece.color != other_piece.color {
-                        let mut new_board = self.clone();
+                        let mut new_board = self.next_board();
                         new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
                         let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
@@ -635,7 +700,7 @@ impl NormalBoard {
 
                 match self.get_piece(new_letter, new_number) {
                     Ok(None) => {
-                        let mut new_board = self.clone();
+                        let mut new_board = self.next_board();
                         new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
                         let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
@@ -643,7 +708,7 @@ impl NormalBoard {
                     },
                     Ok(Some(other_piece)) => {
                         if piece.color != other_piece.color {
-                            let mut new_board = self.clone();
+                            let mut new_board = self.next_board();
                             new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
     
                             let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
@@ -695,7 +760,7 @@ impl NormalBoard {
             };
 
             if generate_move {
-                let mut new_board = self.clone();
+                let mut new_board = self.next_board();
                 new_board.move_piece(letter as i32, number as i32, new_letter, new_number)?;
 
                 let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(new_letter as usize), get_number(new_number as usize));
@@ -713,11 +778,11 @@ impl NormalBoard {
                         if let Ok(None) = self.get_piece(2, number as i32) {
                             if let Ok(None) = self.get_piece(3, number as i32) {
                                 if !opponent_reach_board[2][number] && !opponent_reach_board[3][number] && !opponent_reach_board[4][number] {
-                                    let mut new_board = self.clone();
+                                    let mut new_board = self.next_board();
                                     new_board.move_piece(letter as i32, number as i32, 2, number as i32)?;
                                     new_board.move_piece(0, number as i32, 3, number as i32)?;
 
-                                    let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(3), get_number(number));
+                                    let mov_str = format!("{}{} {}{}", get_letter(letter), get_number(number), get_letter(2), get_number(number));
                                     possible_moves.push((mov_str, new_board));
                                 }
                             }
@@ -734,7 +799,7 @@ impl NormalBoard {
                     if let Ok(None) = self.get_piece(5, number as i32) {
                         if let Ok(None) = self.get_piece(6, number as i32) {
                             if !opponent_reach_board[4][number] && !opponent_reach_board[5][number] && !opponent_reach_board[6][number] {
-                                let mut new_board = self.clone();
+                                let mut new_board = self.next_board();
                                 new_board.move_piece(letter as i32, number as i32, 6, number as i32)?;
                                 new_board.move_piece(7, number as i32, 5, number as i32)?;
 
@@ -765,69 +830,110 @@ impl NormalBoard {
     }
 
 
+    // Fills in the piece, capture and flags of a generated move string from this board
+    fn create_move(&self, mov_str: &str) -> Result<Move, ChessError> {
+        let (from, to, promotion) = parse_coordinates(mov_str).ok_or(ChessError::InvalidMoveString)?;
+        let (from_letter, from_number) = ((from & 7) as i32, (from >> 3) as i32);
+        let (to_letter, to_number) = ((to & 7) as i32, (to >> 3) as i32);
+
+        let piece = self.get_piece(from_letter, from_number)?.ok_or(ChessError::InvalidMove)?.typ;
+        let mut mov = Move::new(from, to, piece);
+        if let Some(typ) = promotion {
+            mov = mov.with_promotion(typ);
+        }
+
+        Ok(match self.get_piece(to_letter, to_number)? {
+            Some(captured) => mov.with_capture(captured.typ),
+            None if piece == PieceType::Pawn && from_letter != to_letter => mov.with_capture(PieceType::Pawn).with_flags(EN_PASSANT),
+            None if piece == PieceType::Pawn && (from_number - to_number).abs() == 2 => mov.with_flags(DOUBLE_PAWN_PUSH),
+            None if piece == PieceType::King && (from_letter - to_letter).abs() == 2 => mov.with_flags(CASTLING),
+            None => mov
+        })
+    }
+
     // Check if the game has ended for the given player
-    pub fn check_for_game_end(&self, prev_board: Option<&NormalBoard>, turn: PieceColor) -> Result<EndType, ChessError> {
-        if self.generate_possible_moves(prev_board, turn)?.len() == 0 {
+    pub fn check_for_game_end(&self) -> Result<EndType, ChessError> {
+        let turn = self.turn;
+        if self.generate_possible_moves()?.len() == 0 {
             let reach_board = self.generate_reachable_tiles_board(turn.opposite_color());
 
             if let Ok((letter, number)) = self.get_king_pos(turn) {
                 if reach_board[letter][number] {
                     return Ok(EndType::Checkmate(turn.opposite_color()));
                 } else {
-                    return Ok(EndType::Tie);
+                    return Ok(EndType::Tie(TieType::Stalemate));
                 }
             }
         }
 
+        if self.halfmove_clock >= FIFTY_MOVE_RULE_PLIES {
+            return Ok(EndType::Tie(TieType::FiftyMoveRule));
+        }
+
+        if board_to_bitboard(self).has_insufficient_material() {
+            return Ok(EndType::Tie(TieType::InsufficientMaterial));
+        }
+
         Ok(EndType::NoEnd)
     }
+}
+
+impl ChessBoardContract for NormalBoard {
+    // The board before the move, as the generated boards are not updated in place
+    type Undo = NormalBoard;
+
+    fn generate_moves(&self, _: &Constants) -> Result<Vec<Move>, ChessError> {
+        self.generate_possible_moves()?
+            .iter()
+            .map(|(mov_str, _)| self.create_move(mov_str))
+            .collect()
+    }
 
-    pub fn check_repetition(&self, board_history: &Vec<NormalBoard>) -> EndType {
-        let mut count = 0;
+    fn make_move(&mut self, mov: Move) -> Result<NormalBoard, ChessError> {
+        let squares = Some((mov.source_square(), mov.target_square(), mov.promotion()));
+        let (_, new_board) = self.generate_possible_moves()?
+                                 .into_iter()
+                                 .find(|(generated, _)| parse_coordinates(generated) == squares)
+                                 .ok_or(ChessError::InvalidMove)?;
 
-        for b in board_history {
-            if self.check_board_equality(b) {
-                count += 1;
-            }
-        }
+        Ok(std::mem::replace(self, new_board))
+    }
 
-        if count >= 3 {
-            return EndType::Tie;
-        }
+    fn unmake_move(&mut self, _: Move, undo: NormalBoard) {
+        *self = undo;
+    }
 
-        EndType::NoEnd
+    fn make_null_move(&mut self) -> NormalBoard {
+        let new_board = self.next_board();
+        std::mem::replace(self, new_board)
     }
 
-    fn check_board_equality(&self, other: &NormalBoard) -> bool {
-        for i in 0..8 {
-            for j in 0..8 {
-                if self.board[i][j].is_none() && !other.board[i][j].is_none() {
-                    return false;
-                }
+    fn unmake_null_move(&mut self, undo: NormalBoard) {
+        *self = undo;
+    }
 
-                if let Some(piece) = &self.board[i][j] {
-                    if let Some(other_piece) = &other.board[i][j] {
-                        if piece.typ != other_piece.typ || piece.color != other_piece.color {
-                            return false;
-                        }
-                    } else {
-                        return false;
-                    }
-                }
-            }
-        }
+    fn check_game_end(&self, _: &Constants) -> Result<EndType, ChessError> {
+        self.check_for_game_end()
+    }
 
-        true
+    fn in_check(&self, _: &Constants) -> bool {
+        !Self::check_if_valid_move(self, self.turn)
     }
-}
 
-impl ChessBoardContract for NormalBoard {
-    fn generate_moves(&self, prev_board: Option<&Self>, turn: PieceColor, _: &Constants) -> Result<Vec<(String, Self)>, ChessError> {
-        self.generate_possible_moves(prev_board, turn)
+    fn get_turn(&self) -> PieceColor {
+        self.turn
+    }
+
+    fn get_halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    fn get_fullmove_number(&self) -> u32 {
+        self.fullmove_number
     }
 
-    fn check_game_end(&self, prev_board: Option<&Self>, turn: PieceColor, _: &Constants) -> Result<EndType, ChessError> {
-        self.check_for_game_end(prev_board, turn)
+    fn get_hash(&self) -> u64 {
+        board_to_bitboard(self).hash
     }
 
     fn board_ascii(&self, use_unicode: bool) -> String {
@@ -838,9 +944,81 @@ impl ChessBoardContract for NormalBoard {
         NormalBoard::new_start_board()
     }
 
+    fn from_fen(fen: &str) -> Result<Self, ChessError> {
+        Ok(bitboard_to_board(&Position::from_fen(fen)?))
+    }
+
+    fn to_fen(&self) -> String {
+        board_to_bitboard(self).to_fen()
+    }
+
     fn get_value_of_pieces(&self, piece_values: [i32; 6]) -> i32 {
         self.iter().map(|(_, piece)| {
             piece_values[piece.typ as usize] * piece.color.side_const()
         }).sum()
     }
+
+    fn get_value_of_placements(&self, placement_scores: &[[[i32; 8]; 8]; 6]) -> i32 {
+        self.iter().map(|((letter, number), piece)| {
+            match piece.color {
+                PieceColor::White => placement_scores[piece.typ as usize][letter][number],
+                PieceColor::Black => -placement_scores[piece.typ as usize][letter][7 - number]
+            }
+        }).sum()
+    }
+
+    fn get_piece_count(&self, typ: PieceType) -> u32 {
+        self.iter().filter(|(_, piece)| piece.typ == typ).count() as u32
+    }
+
+    fn has_non_pawn_material(&self, color: PieceColor) -> bool {
+        self.iter().any(|(_, piece)| piece.color == color && piece.typ != PieceType::Pawn && piece.typ != PieceType::King)
+    }
+}
+
+// Kings and rooks count as moved unless they still have a castling right, and pawns
+// count as moved unless they are on their starting row
+pub fn bitboard_to_board(bb: &BitBoard) -> NormalBoard {
+    let mut res = NormalBoard::new_empty_board();
+
+    let unmoved_squares = [
+        (WHITE_KING_SIDE_CASTLE, [4, 7]),
+        (WHITE_QUEEN_SIDE_CASTLE, [4, 0]),
+        (BLACK_KING_SIDE_CASTLE, [60, 63]),
+        (BLACK_QUEEN_SIDE_CASTLE, [60, 56])
+    ];
+
+    for i in 0..64 {
+        let num = 1 << i;
+        let piece_num = match (0..12).find(|piece_num| bb[*piece_num] & num != 0) {
+            Some(piece_num) => piece_num,
+            None => continue
+        };
+
+        let color = if piece_num < 6 { PieceColor::White } else { PieceColor::Black };
+        let typ = PIECE_TYPES[piece_num % 6];
+        let (letter, number) = (i % 8, i / 8);
+
+        let mut piece = ChessPiece::new(typ, color);
+        piece.moved = match typ {
+            PieceType::Pawn => number != if color == PieceColor::White { 1 } else { 6 },
+            PieceType::King | PieceType::Rook => !unmoved_squares.iter().any(|(rights, squares)| bb.castling_rights & rights != 0 && squares.contains(&i)),
+            _ => false
+        };
+
+        res.board[letter][number] = Some(piece);
+    }
+
+    res.turn = bb.turn;
+    res.en_passant = match bb.en_passant {
+        0 => None,
+        en_passant => {
+            let square = en_passant.trailing_zeros() as usize;
+            Some((square % 8, square / 8))
+        }
+    };
+    res.halfmove_clock = bb.halfmove_clock;
+    res.fullmove_number = bb.fullmove_number;
+
+    res
 }
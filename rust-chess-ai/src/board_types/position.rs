@@ -0,0 +1,454 @@
+use crate::enums::{
+    piece_color::PieceColor,
+    piece_type::{PieceType, PIECE_TYPES},
+    chess_error::ChessError
+};
+
+use crate::functions::{
+    get_letter,
+    get_number,
+    pos_to_num
+};
+
+use crate::board_types::zobrist::ZOBRIST_KEYS;
+use crate::board_types::chess_move::Move;
+
+use std::ops::{Index, IndexMut};
+
+pub const WHITE_KING_SIDE_CASTLE: u8 = 1;
+pub const WHITE_QUEEN_SIDE_CASTLE: u8 = 2;
+pub const BLACK_KING_SIDE_CASTLE: u8 = 4;
+pub const BLACK_QUEEN_SIDE_CASTLE: u8 = 8;
+pub const ALL_CASTLING_RIGHTS: u8 = 15;
+
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+// Squares where letter + number is odd, starting with b1
+const LIGHT_SQUARES: u64 = 0x55AA55AA55AA55AA;
+
+// Half moves without captures or pawn moves before the game is drawn
+pub const FIFTY_MOVE_RULE_PLIES: u32 = 100;
+
+const FEN_CASTLING: [(char, u8); 4] = [('K', WHITE_KING_SIDE_CASTLE), ('Q', WHITE_QUEEN_SIDE_CASTLE), ('k', BLACK_KING_SIDE_CASTLE), ('q', BLACK_QUEEN_SIDE_CASTLE)];
+
+// One bitboard per PieceNum together with the rest of the game state, so a position
+// can be continued without knowing the boards that came before it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub pieces: [u64; 12],
+    pub turn: PieceColor,
+    pub castling_rights: u8,
+    // The square a pawn skipped over with a double step on the previous move (0 if there is none)
+    pub en_passant: u64,
+    // Moves made since the last capture or pawn move
+    pub halfmove_clock: u32,
+    // Starts at 1 and is incremented after every move by black
+    pub fullmove_number: u32,
+    // Zobrist hash of the pieces, turn, castling rights and en passant square
+    pub hash: u64
+}
+
+impl Position {
+    pub fn new_empty_board() -> Self {
+        Self {
+            pieces: [0; 12],
+            turn: PieceColor::White,
+            castling_rights: 0,
+            en_passant: 0,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            hash: 0
+        }
+    }
+
+    // Neither side can checkmate with only kings and one minor piece, or only bishops on
+    // squares of the same color
+    pub fn has_insufficient_material(&self) -> bool {
+        let pawns_rooks_queens = [PieceType::Pawn, PieceType::Rook, PieceType::Queen].iter()
+            .fold(0, |acc, typ| acc | self[*typ as usize] | self[6 + *typ as usize]);
+        if pawns_rooks_queens != 0 {
+            return false;
+        }
+
+        let knights = self[PieceType::Knight as usize] | self[6 + PieceType::Knight as usize];
+        let bishops = self[PieceType::Bishop as usize] | self[6 + PieceType::Bishop as usize];
+
+        (knights | bishops).count_ones() <= 1
+            || (knights == 0 && (bishops & LIGHT_SQUARES == 0 || bishops & !LIGHT_SQUARES == 0))
+    }
+
+    pub fn compute_hash(&self) -> u64 {
+        let mut hash = 0;
+
+        for piece_num in 0..12 {
+            let mut tmp = self[piece_num];
+            while tmp != 0 {
+                let i = tmp.trailing_zeros() as usize;
+                hash ^= ZOBRIST_KEYS.pieces[piece_num][i];
+                tmp &= tmp - 1;
+            }
+        }
+
+        if self.turn == PieceColor::Black {
+            hash ^= ZOBRIST_KEYS.black_to_move;
+        }
+
+        hash ^ Self::castling_hash(self.castling_rights) ^ Self::en_passant_hash(self.en_passant)
+    }
+
+    fn castling_hash(castling_rights: u8) -> u64 {
+        (0..4).filter(|i| castling_rights & (1 << i) != 0)
+              .fold(0, |acc, i| acc ^ ZOBRIST_KEYS.castling_rights[i])
+    }
+
+    fn en_passant_hash(en_passant: u64) -> u64 {
+        match en_passant {
+            0 => 0,
+            _ => ZOBRIST_KEYS.en_passant[(en_passant.trailing_zeros() % 8) as usize]
+        }
+    }
+}
+
+// The parts of a position that can not be worked out again when a move is taken back
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveUndo {
+    pub castling_rights: u8,
+    pub en_passant: u64,
+    pub halfmove_clock: u32,
+    pub hash: u64
+}
+
+impl Position {
+    // Plays a legal move on this position, updating the hash incrementally
+    pub fn make_move(&mut self, mov: Move) -> MoveUndo {
+        let color = self.turn;
+        let own = color as usize * 6;
+        let (from, to) = (mov.source_square() as usize, mov.target_square() as usize);
+        let piece = mov.piece();
+
+        let undo = MoveUndo {
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            hash: self.hash
+        };
+
+        let mut hash = self.hash ^ ZOBRIST_KEYS.black_to_move;
+        hash ^= Self::castling_hash(self.castling_rights) ^ Self::en_passant_hash(self.en_passant);
+
+        if let Some(typ) = mov.captured() {
+            let opposite = color.opposite_color() as usize * 6;
+            self.toggle_piece(opposite + typ as usize, captured_square(mov, color), &mut hash);
+        }
+
+        self.toggle_piece(own + piece as usize, from, &mut hash);
+        self.toggle_piece(own + mov.promotion().unwrap_or(piece) as usize, to, &mut hash);
+
+        if mov.is_castling() {
+            let (rook_from, rook_to) = castling_rook_squares(from, to);
+            self.toggle_piece(own + PieceType::Rook as usize, rook_from, &mut hash);
+            self.toggle_piece(own + PieceType::Rook as usize, rook_to, &mut hash);
+        }
+
+        self.en_passant = match mov.is_double_pawn_push() {
+            true => 1 << ((from + to) / 2),
+            false => 0
+        };
+        self.castling_rights = update_castling_rights(self.castling_rights, from as u64, to as u64);
+        hash ^= Self::castling_hash(self.castling_rights) ^ Self::en_passant_hash(self.en_passant);
+
+        self.halfmove_clock = match piece == PieceType::Pawn || mov.is_capture() {
+            true => 0,
+            false => self.halfmove_clock + 1
+        };
+        if color == PieceColor::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = color.opposite_color();
+        self.hash = hash;
+
+        undo
+    }
+
+    // Takes back the last move made with make_move, given what it returned
+    pub fn unmake_move(&mut self, mov: Move, undo: MoveUndo) {
+        let color = self.turn.opposite_color();
+        let own = color as usize * 6;
+        let (from, to) = (mov.source_square() as usize, mov.target_square() as usize);
+        let piece = mov.piece();
+        // The hash is restored from the undo, so it does not have to be updated
+        let mut hash = 0;
+
+        self.toggle_piece(own + mov.promotion().unwrap_or(piece) as usize, to, &mut hash);
+        self.toggle_piece(own + piece as usize, from, &mut hash);
+
+        if let Some(typ) = mov.captured() {
+            self.toggle_piece(self.turn as usize * 6 + typ as usize, captured_square(mov, color), &mut hash);
+        }
+
+        if mov.is_castling() {
+            let (rook_from, rook_to) = castling_rook_squares(from, to);
+            self.toggle_piece(own + PieceType::Rook as usize, rook_to, &mut hash);
+            self.toggle_piece(own + PieceType::Rook as usize, rook_from, &mut hash);
+        }
+
+        if color == PieceColor::Black {
+            self.fullmove_number -= 1;
+        }
+        self.turn = color;
+        self.castling_rights = undo.castling_rights;
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.hash = undo.hash;
+    }
+
+    // Passes the turn to the other player, as if they could move twice in a row
+    pub fn make_null_move(&mut self) -> MoveUndo {
+        let undo = MoveUndo {
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            hash: self.hash
+        };
+
+        self.hash ^= ZOBRIST_KEYS.black_to_move ^ Self::en_passant_hash(self.en_passant);
+        self.en_passant = 0;
+        self.halfmove_clock += 1;
+        if self.turn == PieceColor::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = self.turn.opposite_color();
+
+        undo
+    }
+
+    pub fn unmake_null_move(&mut self, undo: MoveUndo) {
+        self.turn = self.turn.opposite_color();
+        if self.turn == PieceColor::Black {
+            self.fullmove_number -= 1;
+        }
+        self.en_passant = undo.en_passant;
+        self.halfmove_clock = undo.halfmove_clock;
+        self.hash = undo.hash;
+    }
+
+    // The type of the piece of the color starting at offset (0 for white, 6 for black) on the square
+    pub fn piece_type_at(&self, offset: usize, square: usize) -> Option<PieceType> {
+        PIECE_TYPES.iter().copied().find(|typ| self[offset + *typ as usize] & (1 << square) != 0)
+    }
+
+    fn toggle_piece(&mut self, piece_num: usize, square: usize, hash: &mut u64) {
+        self[piece_num] ^= 1 << square;
+        *hash ^= ZOBRIST_KEYS.pieces[piece_num][square];
+    }
+}
+
+// The pawn taken en passant is next to the square the capturing pawn moves to
+fn captured_square(mov: Move, color: PieceColor) -> usize {
+    let to = mov.target_square() as usize;
+    match (mov.is_en_passant(), color) {
+        (false, _) => to,
+        (true, PieceColor::White) => to - 8,
+        (true, PieceColor::Black) => to + 8
+    }
+}
+
+// The squares the rook moves from and to when the king castles from and to the given squares
+fn castling_rook_squares(king_from: usize, king_to: usize) -> (usize, usize) {
+    match king_to > king_from {
+        true => (king_from + 3, king_from + 1),
+        false => (king_from - 4, king_from - 1)
+    }
+}
+
+// Moving from or to the starting square of a king or rook removes the castling rights that depend on it
+pub fn update_castling_rights(castling_rights: u8, from: u64, to: u64) -> u8 {
+    let mut res = castling_rights;
+
+    for square in [from, to] {
+        res &= match square {
+            0 => !WHITE_QUEEN_SIDE_CASTLE,
+            4 => !(WHITE_KING_SIDE_CASTLE | WHITE_QUEEN_SIDE_CASTLE),
+            7 => !WHITE_KING_SIDE_CASTLE,
+            56 => !BLACK_QUEEN_SIDE_CASTLE,
+            60 => !(BLACK_KING_SIDE_CASTLE | BLACK_QUEEN_SIDE_CASTLE),
+            63 => !BLACK_KING_SIDE_CASTLE,
+            _ => ALL_CASTLING_RIGHTS
+        };
+    }
+
+    res
+}
+
+impl Position {
+    // The halfmove clock and fullmove number may be left out, in which case they start at 0 and 1
+    pub fn from_fen(fen: &str) -> Result<Self, ChessError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() != 4 && fields.len() != 6 {
+            return Err(ChessError::InvalidFenFieldCount);
+        }
+
+        let mut res = Self::new_empty_board();
+
+        // Board, from the eighth row down to the first
+        let rows: Vec<&str> = fields[0].split('/').collect();
+        if rows.len() != 8 {
+            return Err(ChessError::InvalidFenBoard);
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            let number = 7 - i as u64;
+            let mut letter = 0;
+
+            for c in row.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    if empty == 0 || empty > 8 {
+                        return Err(ChessError::InvalidFenBoard);
+                    }
+                    letter += empty as u64;
+                    continue;
+                }
+
+                let typ = PieceType::from_char(c).ok_or(ChessError::InvalidFenBoard)?;
+                let color = if c.is_ascii_uppercase() { PieceColor::White } else { PieceColor::Black };
+                if letter > 7 {
+                    return Err(ChessError::InvalidFenBoard);
+                }
+
+                res[color as usize * 6 + typ as usize] |= 1 << pos_to_num(letter, number);
+                letter += 1;
+            }
+
+            if letter != 8 {
+                return Err(ChessError::InvalidFenBoard);
+            }
+        }
+
+        for color in [PieceColor::White, PieceColor::Black] {
+            if res[color as usize * 6 + PieceType::King as usize].count_ones() != 1 {
+                return Err(ChessError::InvalidFenBoard);
+            }
+        }
+
+        res.turn = match fields[1] {
+            "w" => PieceColor::White,
+            "b" => PieceColor::Black,
+            _ => return Err(ChessError::InvalidFenTurn)
+        };
+
+        if fields[2] != "-" {
+            for c in fields[2].chars() {
+                let rights = match FEN_CASTLING.iter().find(|(fen_char, _)| *fen_char == c) {
+                    Some((_, rights)) => *rights,
+                    None => return Err(ChessError::InvalidFenCastling)
+                };
+                if res.castling_rights & rights != 0 {
+                    return Err(ChessError::InvalidFenCastling);
+                }
+                res.castling_rights |= rights;
+            }
+        }
+
+        if fields[3] != "-" {
+            let characters: Vec<char> = fields[3].chars().collect();
+            if characters.len() != 2 || !('a'..='h').contains(&characters[0]) {
+                return Err(ChessError::InvalidFenEnPassant);
+            }
+
+            // The skipped square is behind the pawn that just moved
+            let letter = characters[0] as u64 - 'a' as u64;
+            let number = match (characters[1], res.turn) {
+                ('6', PieceColor::White) => 5,
+                ('3', PieceColor::Black) => 2,
+                _ => return Err(ChessError::InvalidFenEnPassant)
+            };
+            res.en_passant = 1 << pos_to_num(letter, number);
+        }
+
+        if fields.len() == 6 {
+            res.halfmove_clock = fields[4].parse().map_err(|_| ChessError::InvalidFenClock)?;
+            res.fullmove_number = fields[5].parse().map_err(|_| ChessError::InvalidFenClock)?;
+            if res.fullmove_number == 0 {
+                return Err(ChessError::InvalidFenClock);
+            }
+        }
+
+        res.hash = res.compute_hash();
+
+        Ok(res)
+    }
+
+    pub fn to_fen(self) -> String {
+        let mut res = String::with_capacity(90);
+
+        for number in (0..8).rev() {
+            let mut empty = 0;
+
+            for letter in 0..8 {
+                let num = 1 << pos_to_num(letter, number);
+                let piece = (0..12).find(|i| self[*i] & num != 0);
+
+                match piece {
+                    Some(i) => {
+                        if empty > 0 {
+                            res.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+
+                        let c = PIECE_TYPES[i % 6].get_char();
+                        res.push(if i < 6 { c.to_ascii_uppercase() } else { c });
+                    },
+                    None => empty += 1
+                }
+            }
+
+            if empty > 0 {
+                res.push_str(&empty.to_string());
+            }
+            if number > 0 {
+                res.push('/');
+            }
+        }
+
+        res.push_str(match self.turn {
+            PieceColor::White => " w ",
+            PieceColor::Black => " b "
+        });
+
+        if self.castling_rights == 0 {
+            res.push('-');
+        }
+        for (c, rights) in FEN_CASTLING {
+            if self.castling_rights & rights != 0 {
+                res.push(c);
+            }
+        }
+
+        res.push(' ');
+        if self.en_passant == 0 {
+            res.push('-');
+        } else {
+            let square = self.en_passant.trailing_zeros() as usize;
+            res.push(get_letter(square & 7));
+            res.push(get_number(square >> 3));
+        }
+
+        res.push_str(&format!(" {} {}", self.halfmove_clock, self.fullmove_number));
+
+        res
+    }
+}
+
+impl Index<usize> for Position {
+    type Output = u64;
+
+    fn index(&self, index: usize) -> &u64 {
+        &self.pieces[index]
+    }
+}
+
+impl IndexMut<usize> for Position {
+    fn index_mut(&mut self, index: usize) -> &mut u64 {
+        &mut self.pieces[index]
+    }
+}
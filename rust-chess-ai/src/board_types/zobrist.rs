@@ -0,0 +1,62 @@
+// Random keys for Zobrist hashing. A position's hash is the xor of the keys for every
+// piece on its square, the side to move, each castling right and the en passant column
+
+pub struct ZobristKeys {
+    pub pieces: [[u64; 64]; 12],
+    pub black_to_move: u64,
+    pub castling_rights: [u64; 4],
+    pub en_passant: [u64; 8]
+}
+
+pub static ZOBRIST_KEYS: ZobristKeys = generate_keys();
+
+// Xorshift, so the keys are the same every run and can be built at compile time
+const fn next_random(state: u64) -> u64 {
+    let mut x = state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
+
+const fn generate_keys() -> ZobristKeys {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    let mut pieces = [[0; 64]; 12];
+    let mut i = 0;
+    while i < 12 {
+        let mut j = 0;
+        while j < 64 {
+            state = next_random(state);
+            pieces[i][j] = state;
+            j += 1;
+        }
+        i += 1;
+    }
+
+    state = next_random(state);
+    let black_to_move = state;
+
+    let mut castling_rights = [0; 4];
+    let mut i = 0;
+    while i < 4 {
+        state = next_random(state);
+        castling_rights[i] = state;
+        i += 1;
+    }
+
+    let mut en_passant = [0; 8];
+    let mut i = 0;
+    while i < 8 {
+        state = next_random(state);
+        en_passant[i] = state;
+        i += 1;
+    }
+
+    ZobristKeys {
+        pieces,
+        black_to_move,
+        castling_rights,
+        en_passant
+    }
+}
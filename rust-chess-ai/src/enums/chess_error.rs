@@ -1,4 +1,3 @@
-
 #[derive(PartialEq, Clone, Copy, Debug)]
 pub enum ChessError {
     InvalidMove = 0,
@@ -6,5 +5,15 @@ pub enum ChessError {
     NoKing = 2,
     OutsideBounds = 3,
     InvalidMoveString = 4,
-    EndWithNoEnd = 5
+    EndWithNoEnd = 5,
+    InvalidFenFieldCount = 6,
+    InvalidFenBoard = 7,
+    InvalidFenTurn = 8,
+    InvalidFenCastling = 9,
+    InvalidFenEnPassant = 10,
+    InvalidFenClock = 11,
+    SearchStopped = 12,
+    AmbiguousMove = 13,
+    FileError = 14,
+    InvalidPgn = 15
 }
@@ -1,8 +1,24 @@
-use crate::enums::piece_color::PieceColor;
+use crate::enums::{
+    piece_color::PieceColor,
+    tie_type::TieType
+};
 
 #[derive(PartialEq, Clone, Copy, Debug)]
 pub enum EndType {
     NoEnd,
-    Tie,
+    Tie(TieType),
+    // Holds the color of the winner
     Checkmate(PieceColor)
 }
+
+impl EndType {
+    // The result as it is written in PGN
+    pub fn get_result_string(&self) -> String {
+        match self {
+            EndType::NoEnd => "*".to_string(),
+            EndType::Tie(_) => "1/2-1/2".to_string(),
+            EndType::Checkmate(PieceColor::White) => "1-0".to_string(),
+            EndType::Checkmate(PieceColor::Black) => "0-1".to_string()
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::EvaluationFunction;
+use crate::traits::chess_board_contract::ChessBoardContract;
+use crate::evaluation_functions::{
+    board_piece_evaluation::board_piece_evaluation,
+    piece_square_evaluation::piece_square_evaluation
+};
+
+// The evaluation functions that can be picked by name, like with the Evaluation option in UCI
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum EvaluationType {
+    Material = 0,
+    PieceSquare = 1
+}
+
+pub const EVALUATION_TYPES: [EvaluationType; 2] = [EvaluationType::Material, EvaluationType::PieceSquare];
+
+impl EvaluationType {
+    pub fn get_function<T: ChessBoardContract>(&self) -> EvaluationFunction<T> {
+        match self {
+            EvaluationType::Material => board_piece_evaluation,
+            EvaluationType::PieceSquare => piece_square_evaluation
+        }
+    }
+
+    pub fn get_string(&self) -> String {
+        match self {
+            EvaluationType::Material => "Material".to_string(),
+            EvaluationType::PieceSquare => "PieceSquare".to_string()
+        }
+    }
+
+    pub fn from_string(name: &str) -> Option<EvaluationType> {
+        EVALUATION_TYPES.iter().copied().find(|typ| typ.get_string().eq_ignore_ascii_case(name))
+    }
+}
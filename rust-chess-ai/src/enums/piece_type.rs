@@ -1,4 +1,3 @@
-
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum PieceType {
     Pawn = 0,
@@ -8,3 +7,34 @@ pub enum PieceType {
     Queen = 4,
     King = 5
 }
+
+// In the same order as the values, so PIECE_TYPES[typ as usize] == typ
+pub const PIECE_TYPES: [PieceType; 6] = [PieceType::Pawn, PieceType::Rook, PieceType::Knight, PieceType::Bishop, PieceType::Queen, PieceType::King];
+
+// The pieces a pawn can be promoted to, in the order the moves are generated
+pub const PROMOTION_PIECES: [PieceType; 4] = [PieceType::Queen, PieceType::Rook, PieceType::Bishop, PieceType::Knight];
+
+impl PieceType {
+    pub fn get_char(&self) -> char {
+        match self {
+            PieceType::Pawn => 'p',
+            PieceType::Rook => 'r',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k'
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<PieceType> {
+        match c.to_ascii_lowercase() {
+            'p' => Some(PieceType::Pawn),
+            'r' => Some(PieceType::Rook),
+            'n' => Some(PieceType::Knight),
+            'b' => Some(PieceType::Bishop),
+            'q' => Some(PieceType::Queen),
+            'k' => Some(PieceType::King),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,18 @@
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TieType {
+    Stalemate,
+    ThreefoldRepetition,
+    FiftyMoveRule,
+    InsufficientMaterial
+}
+
+impl TieType {
+    pub fn get_string(&self) -> String {
+        match self {
+            TieType::Stalemate => "stalemate".to_string(),
+            TieType::ThreefoldRepetition => "threefold repetition".to_string(),
+            TieType::FiftyMoveRule => "the fifty-move rule".to_string(),
+            TieType::InsufficientMaterial => "insufficient material".to_string()
+        }
+    }
+}
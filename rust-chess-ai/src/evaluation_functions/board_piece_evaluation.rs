@@ -3,37 +3,28 @@ use crate::traits::{
     chess_board_contract::ChessBoardContract
 };
 
-use crate::enums::{
-    piece_color::PieceColor,
-    end_type::EndType
-};
+use crate::enums::end_type::EndType;
 
 use crate::board_types::bitboard::{
     Constants
 };
+use crate::evaluation_functions::score::mate_score;
 
-const PIECE_VALUES: [i32; 6] = [100, 500, 300, 300, 900, 0];
-
-pub fn board_piece_evaluation<T: ChessBoardContract>(board: &T, prev_board: Option<&T>, _: &Vec<T>, depth: i32, constants: &Constants) -> Result<i32, ChessError> {
-    match board.check_game_end(prev_board, PieceColor::White, constants)? {
-        EndType::Checkmate(_) => {
-            return Ok(<i32>::min_value() / 2 + depth);
-        },
-        EndType::Tie => {
-            return Ok(0);
-        },
-        EndType::NoEnd => ()
-    }
+pub const PIECE_VALUES: [i32; 6] = [100, 500, 300, 300, 900, 0];
 
-    match board.check_game_end(prev_board, PieceColor::White, constants)? {
-        EndType::Checkmate(_) => {
-            return Ok(<i32>::max_value() / 2 - depth);
-        },
-        EndType::Tie => {
-            return Ok(0);
-        },
-        EndType::NoEnd => ()
+pub fn board_piece_evaluation<T: ChessBoardContract>(board: &T, ply: i32, constants: &Constants) -> Result<i32, ChessError> {
+    if let Some(score) = game_end_evaluation(board, ply, constants)? {
+        return Ok(score);
     }
 
     Ok(board.get_value_of_pieces(PIECE_VALUES))
 }
+
+// The score of a position where the game has ended, shared by the evaluation functions
+pub fn game_end_evaluation<T: ChessBoardContract>(board: &T, ply: i32, constants: &Constants) -> Result<Option<i32>, ChessError> {
+    match board.check_game_end(constants)? {
+        EndType::Checkmate(winner) => Ok(Some(mate_score(winner, ply))),
+        EndType::Tie(_) => Ok(Some(0)),
+        EndType::NoEnd => Ok(None)
+    }
+}
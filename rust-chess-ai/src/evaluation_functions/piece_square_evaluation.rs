@@ -0,0 +1,103 @@
+use crate::enums::{
+    chess_error::ChessError,
+    piece_type::PIECE_TYPES
+};
+use crate::traits::chess_board_contract::ChessBoardContract;
+use crate::board_types::bitboard::Constants;
+use crate::evaluation_functions::board_piece_evaluation::{game_end_evaluation, PIECE_VALUES};
+
+// How much each piece counts towards the game phase. With all pieces on the board the phase is
+// MAX_PHASE, the middlegame, and it goes down to 0, the endgame, as they are traded off
+const PHASE_WEIGHTS: [i32; 6] = [0, 2, 1, 1, 4, 0];
+const MAX_PHASE: i32 = 24;
+
+// From white's point of view and indexed [letter][number]
+const PAWN_PLACEMENT_SCORE: [[i32; 8]; 8] = [[0,   5,   5,  0,  5, 10, 50, 0],
+                                             [0,  10,  -5,  0,  5, 10, 50, 0],
+                                             [0,  10, -10,  0, 10, 20, 50, 0],
+                                             [0, -20,   0, 20, 25, 30, 50, 0],
+                                             [0, -20,   0, 20, 25, 30, 50, 0],
+                                             [0,  10, -10,  0, 10, 20, 50, 0],
+                                             [0,  10,  -5,  0,  5, 10, 50, 0],
+                                             [0,   5,   5,  0,  5, 10, 50, 0]];
+
+const ROOK_PLACEMENT_SCORE: [[i32; 8]; 8] = [[0, -5, -5, -5, -5, -5,  5, 0],
+                                             [0,  0,  0,  0,  0,  0, 10, 0],
+                                             [0,  0,  0,  0,  0,  0, 10, 0],
+                                             [5,  0,  0,  0,  0,  0, 10, 0],
+                                             [5,  0,  0,  0,  0,  0, 10, 0],
+                                             [0,  0,  0,  0,  0,  0, 10, 0],
+                                             [0,  0,  0,  0,  0,  0, 10, 0],
+                                             [0, -5, -5, -5, -5, -5,  5, 0]];
+
+const KNIGHT_PLACEMENT_SCORE: [[i32; 8]; 8] = [[-50, -40, -30, -30, -30, -30, -40, -50],
+                                               [-40, -20,   5,   0,   5,   0, -20, -40],
+                                               [-30,   0,  10,  15,  15,  10,   0, -30],
+                                               [-30,   5,  15,  20,  20,  15,   0, -30],
+                                               [-30,   5,  15,  20,  20,  15,   0, -30],
+                                               [-30,   0,  10,  15,  15,  10,   0, -30],
+                                               [-40, -20,   5,   0,   5,   0, -20, -40],
+                                               [-50, -40, -30, -30, -30, -30, -40, -50]];
+
+const BISHOP_PLACEMENT_SCORE: [[i32; 8]; 8] = [[-20, -10, -10, -10, -10, -10, -10, -20],
+                                               [-10,   5,  10,   0,   5,   0,   0, -10],
+                                               [-10,   0,  10,  10,   5,   5,   0, -10],
+                                               [-10,   0,  10,  10,  10,  10,   0, -10],
+                                               [-10,   0,  10,  10,  10,  10,   0, -10],
+                                               [-10,   0,  10,  10,   5,   5,   0, -10],
+                                               [-10,   5,  10,   0,   5,   0,   0, -10],
+                                               [-20, -10, -10, -10, -10, -10, -10, -20]];
+
+const QUEEN_PLACEMENT_SCORE: [[i32; 8]; 8] = [[-20, -10, -10, 0, -5, -10, -10, -20],
+                                              [-10,   0,   5, 0,  0,   0,   0,  10],
+                                              [-10,   5,   5, 5,  5,   5,   0, -10],
+                                              [ -5,   0,   5, 5,  5,   5,   0,  -5],
+                                              [ -5,   0,   5, 5,  5,   5,   0,  -5],
+                                              [-10,   0,   5, 5,  5,   5,   0, -10],
+                                              [-10,   0,   5, 0,  0,   0,   0,  10],
+                                              [-20, -10, -10, 0, -5, -10, -10, -20]];
+
+const KING_EARLY_PLACEMENT_SCORE: [[i32; 8]; 8] = [[20, 20, -10, -20, -30, -30, -30, -30],
+                                                   [30, 20, -20, -30, -40, -40, -40, -40],
+                                                   [10,  0, -20, -30, -40, -40, -40, -40],
+                                                   [ 0,  0, -20, -40, -50, -50, -50, -50],
+                                                   [ 0,  0, -20, -40, -50, -50, -50, -50],
+                                                   [10,  0, -20, -30, -40, -40, -40, -40],
+                                                   [30, 20, -20, -30, -40, -40, -40, -40],
+                                                   [20, 20, -10, -20, -30, -30, -30, -30]];
+
+const KING_LATE_PLACEMENT_SCORE: [[i32; 8]; 8] = [[-50, -30, -30, -30, -30, -30, -30, -50],
+                                                  [-30, -30, -10, -10, -10, -10, -20, -40],
+                                                  [-30,   0,  20,  30,  30,  20, -10, -30],
+                                                  [-30,   0,  30,  40,  40,  30,   0, -20],
+                                                  [-30,   0,  30,  40,  40,  30,   0, -20],
+                                                  [-30,   0,  20,  30,  30,  20, -10, -30],
+                                                  [-30, -30, -10, -10, -10, -10, -20, -40],
+                                                  [-50, -30, -30, -30, -30, -30, -30, -50]];
+
+// The king should stay behind its pawns while there are pieces to attack it, but come to the
+// center once they are gone. The other pieces use the same table in both
+const MIDDLEGAME_PLACEMENT_SCORES: [[[i32; 8]; 8]; 6] = [PAWN_PLACEMENT_SCORE, ROOK_PLACEMENT_SCORE, KNIGHT_PLACEMENT_SCORE,
+                                                         BISHOP_PLACEMENT_SCORE, QUEEN_PLACEMENT_SCORE, KING_EARLY_PLACEMENT_SCORE];
+const ENDGAME_PLACEMENT_SCORES: [[[i32; 8]; 8]; 6] = [PAWN_PLACEMENT_SCORE, ROOK_PLACEMENT_SCORE, KNIGHT_PLACEMENT_SCORE,
+                                                      BISHOP_PLACEMENT_SCORE, QUEEN_PLACEMENT_SCORE, KING_LATE_PLACEMENT_SCORE];
+
+// Material plus where the pieces stand, blended between the middlegame and endgame scores by
+// how much material is left
+pub fn piece_square_evaluation<T: ChessBoardContract>(board: &T, ply: i32, constants: &Constants) -> Result<i32, ChessError> {
+    if let Some(score) = game_end_evaluation(board, ply, constants)? {
+        return Ok(score);
+    }
+
+    let phase = PIECE_TYPES.iter()
+                           .map(|typ| PHASE_WEIGHTS[*typ as usize] * board.get_piece_count(*typ) as i32)
+                           .sum::<i32>()
+                           // Can be higher after promotions
+                           .min(MAX_PHASE);
+
+    let middlegame = board.get_value_of_placements(&MIDDLEGAME_PLACEMENT_SCORES);
+    let endgame = board.get_value_of_placements(&ENDGAME_PLACEMENT_SCORES);
+    let placements = (middlegame * phase + endgame * (MAX_PHASE - phase)) / MAX_PHASE;
+
+    Ok(board.get_value_of_pieces(PIECE_VALUES) + placements)
+}
@@ -0,0 +1,43 @@
+use crate::enums::piece_color::PieceColor;
+
+// Scores are in centipawns from white's point of view. A mate is scored MATE_SCORE minus the
+// number of plies from the position the search started in, so a shorter mate scores higher, and
+// every score stays far enough from the INFINITY the search starts its window with
+pub const MATE_SCORE: i32 = 1_000_000;
+// Higher than any score. Unlike i32::MIN, -INFINITY can be negated again
+pub const INFINITY: i32 = i32::MAX;
+// No search gets this deep, so any score within this many points of MATE_SCORE is a mate
+const MAX_MATE_PLY: i32 = 1000;
+
+pub fn mate_score(winner: PieceColor, ply: i32) -> i32 {
+    winner.side_const() * (MATE_SCORE - ply)
+}
+
+pub fn is_mate_score(score: i32) -> bool {
+    score.abs() > MATE_SCORE - MAX_MATE_PLY
+}
+
+// The number of moves until mate, negative if black is the one mating, or None if the score
+// is not a mate
+pub fn mate_in_moves(score: i32) -> Option<i32> {
+    match is_mate_score(score) {
+        true => Some(score.signum() * (MATE_SCORE - score.abs() + 1) / 2),
+        false => None
+    }
+}
+
+// The transposition table is shared between positions at different plies, so mates are stored
+// counting from the position itself and converted back when they are read
+pub fn score_to_tt(score: i32, ply: i32) -> i32 {
+    match is_mate_score(score) {
+        true => score + score.signum() * ply,
+        false => score
+    }
+}
+
+pub fn score_from_tt(score: i32, ply: i32) -> i32 {
+    match is_mate_score(score) {
+        true => score - score.signum() * ply,
+        false => score
+    }
+}
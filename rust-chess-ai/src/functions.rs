@@ -35,31 +35,41 @@ pub fn num_to_pos(num: u64) -> (u64, u64) {
     (num & 7, num >> 3)
 }
 
-pub fn validate_move_string(move_str: &String) -> bool {
+// Accepts moves written as "e2 e4" or "e2e4", optionally followed by the piece a pawn is
+// promoted to ("e7e8n"), and returns them in the format used by the generated moves ("e7 e8n")
+pub fn normalize_move_string(move_str: &str) -> Option<String> {
     let valid_letters = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
     let valid_numbers = ['1', '2', '3', '4', '5', '6', '7', '8'];
+    let valid_promotions = ['q', 'r', 'b', 'n'];
 
-    let mut characters = move_str.chars();
+    let mut characters: Vec<char> = move_str.trim().chars().collect();
 
-    if !valid_letters.contains(&mut characters.nth(0).unwrap()) {
-        return false;
+    if characters.len() > 2 && characters[2] == ' ' {
+        characters.remove(2);
     }
 
-    if !valid_numbers.contains(&mut characters.nth(0).unwrap()) {
-        return false;
+    if characters.len() != 4 && characters.len() != 5 {
+        return None;
     }
 
-    if characters.nth(0).unwrap() != ' ' {
-        return false;
+    if !valid_letters.contains(&characters[0]) || !valid_numbers.contains(&characters[1]) {
+        return None;
     }
 
-    if !valid_letters.contains(&mut characters.nth(0).unwrap()) {
-        return false;
+    if !valid_letters.contains(&characters[2]) || !valid_numbers.contains(&characters[3]) {
+        return None;
     }
 
-    if !valid_numbers.contains(&mut characters.nth(0).unwrap()) {
-        return false;
+    let mut res = format!("{}{} {}{}", characters[0], characters[1], characters[2], characters[3]);
+
+    if characters.len() == 5 {
+        let promotion = characters[4].to_ascii_lowercase();
+        if !valid_promotions.contains(&promotion) {
+            return None;
+        }
+        res.push(promotion);
     }
 
-    true
+    Some(res)
 }
+
@@ -10,16 +10,22 @@ use crate::{
     traits::{
         chess_board_contract::ChessBoardContract
     },
-    board_types::bitboard::Constants
+    board_types::{
+        bitboard::Constants,
+        chess_move::Move
+    },
+    notation::move_to_san,
+    pgn::{PgnGame, PgnMove, date_today, clock_string, eval_string}
 };
 
-use std::sync::Arc;
+use std::time::Instant;
 
 const BOARD_HISTORY_START_CAPACITY: usize = 100;
 
 pub struct Game<T> {
     board_history: Vec<T>,
-    turn: PieceColor,
+    // The moves in SAN with the tags and result, so the game can be saved as PGN
+    record: PgnGame,
     constants: Constants
 }
 
@@ -30,125 +36,106 @@ impl<T: ChessBoardContract + Clone> Game<T> {
 
         Self {
             board_history: history,
-            turn: PieceColor::White,
+            record: PgnGame::new(),
             constants: Constants::new()
         }
     }
 
+    pub fn from_fen(fen: &str) -> Result<Self, ChessError> {
+        let mut history = Vec::with_capacity(BOARD_HISTORY_START_CAPACITY);
+        let board = T::from_fen(fen)?;
+
+        let mut record = PgnGame::new();
+        record.set_tag("SetUp", "1");
+        record.set_tag("FEN", &board.to_fen());
+
+        history.push(board);
+
+        Ok(Self {
+            board_history: history,
+            record,
+            constants: Constants::new()
+        })
+    }
+
+    pub fn get_record(&self) -> &PgnGame {
+        &self.record
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        self.record.set_tag(name, value);
+    }
+
     fn clear_console() {
         print!("\x1B[2J\x1B[1;1H");
     }
 
-    pub fn run(mut game: Game<T>, white_player: Player<T>, black_player: Player<T>) -> Result<EndType, ChessError> {
-        let const_ref = Arc::new(game.constants);
-        let win_type = loop {
-            let opponent_color = game.turn.opposite_color();
+    // Plays the move, which has to be legal, and returns how the game ended if it did
+    pub fn play_move(&mut self, mov: Move, comment: Option<String>) -> Result<EndType, ChessError> {
+        let current_board = &self.board_history[self.board_history.len() - 1];
 
-            let history_len = game.board_history.len();
-            let prev_board = if history_len > 1 { Some(&game.board_history[history_len - 2] ) } else { None };
-            let current_board: T = game.board_history[history_len - 1].clone();
+        // Written before the move is made, as the notation depends on the other moves in the position.
+        // Also fails if the move is not legal
+        let san = move_to_san(current_board, mov, &self.constants)?;
+        let new_board = current_board.after_move(mov)?;
 
-            let possible_moves: Vec<(String, T)> = current_board.generate_moves(prev_board, game.turn, &const_ref)?;
+        let end_type = match new_board.check_game_end(&self.constants)? {
+            EndType::NoEnd => new_board.check_repetition(&self.board_history),
+            typ => typ
+        };
 
-            let res: Result<String, ChessError> = match &game.turn {
-                PieceColor::White => (white_player.turn_function)(&current_board, prev_board, &game.board_history, game.turn, &white_player, &const_ref),
-                PieceColor::Black => (black_player.turn_function)(&current_board, prev_board, &game.board_history, game.turn, &black_player, &const_ref)
-            };
+        self.board_history.push(new_board);
+        self.record.moves.push(PgnMove::new(san, comment));
+        self.record.result = end_type.get_result_string();
 
-            let res: String = match res {
-                Ok(res) => {
-                    if res.len() < 5 {
-                        return Err(ChessError::InvalidMoveString);
-                    }
-                    res[0..5].to_string()
-                },
-                Err(err) => {
-                    return Err(err);
-                }
-            };
+        Ok(end_type)
+    }
 
-            if !Self::validate_move_string(&res) {
-                return Err(ChessError::InvalidMoveString);
-            }
+    pub fn run(&mut self, white_player: Player<T>, black_player: Player<T>) -> Result<EndType, ChessError> {
+        self.set_tag("Date", &date_today());
+        self.set_tag("White", &white_player.name);
+        self.set_tag("Black", &black_player.name);
 
-            // for item in &possible_moves {
-            //     println!("{}, {}", item.0, item.0 == res);
-            // }
+        let end_type = loop {
+            let current_board: T = self.board_history[self.board_history.len() - 1].clone();
+            let player = match current_board.get_turn() {
+                PieceColor::White => &white_player,
+                PieceColor::Black => &black_player
+            };
 
-            let filtered_moves = possible_moves.into_iter()
-                                                       .filter(|mov| (*mov).0 == res)
-                                                       .map(|mov| mov.1)
-                                                       .collect::<Vec<_>>();
+            let start_time = Instant::now();
+            let mov = (player.turn_function)(&current_board, &self.board_history, player, &self.constants)?;
 
-            if filtered_moves.len() == 0 {
-                return Err(ChessError::InvalidMove);
+            // How long the move took and what the bots thought of it, written the way most PGN viewers read them
+            let mut comment = format!("[%emt {}]", clock_string(start_time.elapsed()));
+            if let Some(score) = player.last_score.take() {
+                comment = format!("[%eval {}] {}", eval_string(score), comment);
             }
 
+            let end_type = self.play_move(mov, Some(comment))?;
 
-            let new_board = &filtered_moves[0];
-
-            println!("{}\n", new_board.board_ascii(true));
-
-            match new_board.check_game_end(Some(&current_board), opponent_color, &const_ref)? {
-                EndType::NoEnd => (),
-                typ => {
-                    game.board_history.push(new_board.clone());
-                    break typ;
-                }
+            println!("{}\n", self.board_history[self.board_history.len() - 1].board_ascii(true));
+            let san = &self.record.moves[self.record.moves.len() - 1].san;
+            match current_board.get_turn() {
+                PieceColor::White => println!("{}. {}\n", current_board.get_fullmove_number(), san),
+                PieceColor::Black => println!("{}... {}\n", current_board.get_fullmove_number(), san)
             }
 
-            // match &new_board.check_repetition(&game.board_history) {
-            //     EndType::NoEnd => (),
-            //     typ => {
-            //         game.board_history.push(new_board);
-            //         break typ.clone()
-            //     }
-            // }
-
-            game.board_history.push(new_board.clone());
-            game.turn = opponent_color;
+            if end_type != EndType::NoEnd {
+                break end_type;
+            }
         };
 
-        match win_type {
+        match end_type {
             EndType::Checkmate(color) => {
                 println!("{} won by checkmate!", color.get_string());
                 Ok(EndType::Checkmate(color))
             },
-            EndType::Tie => {
-                println!("Game ended in a tie.");
-                Ok(EndType::Tie)
+            EndType::Tie(tie_type) => {
+                println!("Game ended in a tie by {}.", tie_type.get_string());
+                Ok(EndType::Tie(tie_type))
             },
             EndType::NoEnd => Err(ChessError::EndWithNoEnd)
         }
     }
-
-    fn validate_move_string(move_str: &String) -> bool {
-        let valid_letters = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
-        let valid_numbers = ['1', '2', '3', '4', '5', '6', '7', '8'];
-
-        let mut characters = move_str.chars();
-
-        if !valid_letters.contains(&mut characters.nth(0).unwrap()) {
-            return false;
-        }
-
-        if !valid_numbers.contains(&mut characters.nth(0).unwrap()) {
-            return false;
-        }
-
-        if characters.nth(0).unwrap() != ' ' {
-            return false;
-        }
-
-        if !valid_letters.contains(&mut characters.nth(0).unwrap()) {
-            return false;
-        }
-
-        if !valid_numbers.contains(&mut characters.nth(0).unwrap()) {
-            return false;
-        }
-
-        true
-    }
 }
-
@@ -1,14 +1,20 @@
-extern crate work_queue;
 extern crate num_cpus;
-extern crate rustc_hash;
 
 mod game;
 mod tmp;
 mod functions;
+mod uci;
+mod perft;
+mod notation;
+mod pgn;
 
 mod board_types {
     pub mod normalboard;
     pub mod bitboard;
+    pub mod position;
+    pub mod zobrist;
+    pub mod magic;
+    pub mod chess_move;
 }
 
 mod enums {
@@ -18,6 +24,8 @@ mod enums {
     pub mod chess_error;
     pub mod piece_num;
     pub mod piece_type;
+    pub mod tie_type;
+    pub mod evaluation_type;
 }
 
 mod traits {
@@ -29,14 +37,39 @@ mod turn_functions {
     pub mod minimax_move;
 }
 
+mod search {
+    pub mod transposition_table;
+    pub mod iterative_deepening;
+    pub mod search_options;
+    pub mod move_ordering;
+    pub mod search_threads;
+}
+
 mod evaluation_functions {
     pub mod board_piece_evaluation;
+    pub mod piece_square_evaluation;
+    pub mod score;
 }
 
 mod tests {
     mod unit_tests {
+        pub mod common;
         pub mod normalboard_tests;
         pub mod bitboard_tests;
+        pub mod functions_tests;
+        pub mod fen_tests;
+        pub mod zobrist_tests;
+        pub mod transposition_table_tests;
+        pub mod iterative_deepening_tests;
+        pub mod draw_tests;
+        pub mod perft_tests;
+        pub mod differential_tests;
+        pub mod magic_tests;
+        pub mod notation_tests;
+        pub mod pgn_tests;
+        pub mod evaluation_tests;
+        pub mod search_tests;
+        pub mod uci_tests;
     }
 }
 
@@ -44,17 +77,14 @@ mod tests {
 
 
 
-use crate::enums::{
-    piece_color::PieceColor,
-    chess_error::ChessError
-};
+use crate::enums::chess_error::ChessError;
 
 use crate::turn_functions::{
     player_move::player_move
 };
 
 use crate::evaluation_functions::{
-    board_piece_evaluation::board_piece_evaluation
+    piece_square_evaluation::piece_square_evaluation
 };
 
 use board_types::normalboard::NormalBoard;
@@ -63,51 +93,137 @@ use traits::{
 };
 
 use board_types::bitboard::{Constants, BitBoard};
+use board_types::chess_move::Move;
 use turn_functions::minimax_move::minimax_move;
 use crate::game::Game;
+use crate::search::transposition_table::TranspositionTable;
+use crate::search::iterative_deepening::{TimeControl, SearchLimits, MAX_DEPTH, time_budget};
+use crate::search::search_options::SearchOptions;
+use crate::search::search_threads::SearchThreads;
 
+use std::cell::Cell;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-pub type EvaluationFunction<T: ChessBoardContract> = fn(&T, Option<&T>, &Vec<T>, i32, &Constants) -> Result<i32, ChessError>;
+
+const PGN_FILE: &str = "last_game.pgn";
+
+// Scores the position from white's point of view, as described in evaluation_functions::score.
+// The ply is the number of moves made since the position the search started in
+pub type EvaluationFunction<T: ChessBoardContract> = fn(&T, i32, &Constants) -> Result<i32, ChessError>;
 
 pub struct Player<T: 'static + ChessBoardContract> {
-    turn_function: Box<dyn Fn(&T, Option<&T>, &Vec<T>, PieceColor, &Player<T>, &Constants) -> Result<String, ChessError>>,
-    moves_ahead: i32
+    turn_function: Box<dyn Fn(&T, &Vec<T>, &Player<T>, &Constants) -> Result<Move, ChessError>>,
+    // Used for the White and Black tags of the PGN
+    name: String,
+    // Set by the bots to the score of their last move from white's point of view, so it can be
+    // written in the PGN
+    last_score: Cell<Option<i32>>
 }
 
 impl<T: 'static + ChessBoardContract + Clone + Send + Sync> Player<T> {
     pub fn human_player() -> Self {
         Self {
             turn_function: Box::new(player_move),
-            moves_ahead: 0
+            name: "Human".to_string(),
+            last_score: Cell::new(None)
         }
     }
 
-    pub fn minimax_bot(moves_ahead: i32, eval_func: EvaluationFunction<T>, alpha_beta_pruning: bool, multi_threading: bool) -> Self {
+    // The transposition table is kept between moves and takes up tt_size_mb megabytes. So are
+    // the helper threads, when options.threads is more than one
+    pub fn minimax_bot(time_control: TimeControl, eval_func: EvaluationFunction<T>, options: SearchOptions, tt_size_mb: usize) -> Self {
+        let tt = Arc::new(TranspositionTable::new(tt_size_mb));
+        // The helpers outlive the turns, so they get constants of their own instead of the game's
+        let helpers = (options.threads > 1).then(|| SearchThreads::new(options.threads - 1, Arc::new(Constants::new())));
+
+        // Only used with TimeControl::Clock, where the bot keeps track of its own clock
+        let time_left = Cell::new(match time_control {
+            TimeControl::Clock { time, .. } => time,
+            _ => Duration::ZERO
+        });
+
         Self {
             turn_function: {
-                Box::new(move |board: &T, previous_board: Option<&T>, board_history: &Vec<T>, turn: PieceColor, player: &Player<T>, constants: &Constants| -> Result<String, ChessError> {
-                    minimax_move(board, previous_board, board_history, turn, player, eval_func, constants, alpha_beta_pruning, multi_threading)
+                Box::new(move |_: &T, board_history: &Vec<T>, player: &Player<T>, constants: &Constants| -> Result<Move, ChessError> {
+                    let limits = match time_control {
+                        TimeControl::Depth(depth) => SearchLimits { depth, time: None },
+                        TimeControl::MoveTime(time) => SearchLimits { depth: MAX_DEPTH, time: Some(time) },
+                        TimeControl::Clock { increment, .. } => SearchLimits {
+                            depth: MAX_DEPTH,
+                            time: Some(time_budget(time_left.get(), increment, None))
+                        }
+                    };
+
+                    let start_time = Instant::now();
+                    let res = minimax_move(board_history, &limits, eval_func, constants, &options, &tt, helpers.as_ref())?;
+
+                    if let TimeControl::Clock { increment, .. } = time_control {
+                        time_left.set(time_left.get().saturating_sub(start_time.elapsed()) + increment);
+                        println!("Time left: {} seconds", time_left.get().as_millis() as f32 / 1000.);
+                    }
+
+                    player.last_score.set(Some(res.score));
+                    Ok(res.best_move)
                 })
             },
-            moves_ahead: moves_ahead
+            name: "rust-chess-ai".to_string(),
+            last_score: Cell::new(None)
         }
     }
 }
 
 fn main() {
 
-    let new_game: Game<BitBoard> = Game::new();
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(|arg| arg.as_str()) {
+        // Run as a UCI engine for chess GUIs and tournament managers
+        Some("uci") => {
+            uci::uci_loop();
+            return;
+        },
+        Some("perft") => {
+            perft::perft_command(&args[2..]);
+            return;
+        },
+        Some("pgn") => {
+            pgn::pgn_command(&args[2..]);
+            return;
+        },
+        _ => ()
+    }
 
-    // let white_player = Player::minimax_bot(3, board_piece_evaluation, true, false);
+    // "fen <fen>" starts the game from that position instead of the starting position
+    let mut game: Game<BitBoard> = match args.get(1).map(|arg| arg.as_str()) {
+        Some("fen") => match Game::from_fen(&args[2..].join(" ")) {
+            Ok(game) => game,
+            Err(err) => {
+                println!("Invalid FEN: {:?}", err);
+                return;
+            }
+        },
+        _ => Game::new()
+    };
+
+    // let white_player = Player::minimax_bot(TimeControl::Depth(3), piece_square_evaluation, SearchOptions::default(), 64);
     let white_player = Player::human_player();
 
-    let black_player = Player::minimax_bot(5, board_piece_evaluation, true, true);
+    let black_player = Player::minimax_bot(TimeControl::Clock { time: Duration::from_secs(300), increment: Duration::from_secs(3) }, piece_square_evaluation, SearchOptions { threads: num_cpus::get(), ..SearchOptions::default() }, 64);
+
+    game.set_tag("Event", "Terminal game");
+    game.set_tag("Site", "rust-chess-ai");
 
-    let res = Game::run(new_game, white_player, black_player);
+    let res = game.run(white_player, black_player);
 
     match res {
         Ok(_) => print!("Good!"),
         Err(err) => println!("{:?}", err)
     }
 
+    // Also written if the game was stopped by an error, with "*" as the result
+    match game.get_record().write_to_file(PGN_FILE, true) {
+        Ok(()) => println!("\nThe game was saved to {}", PGN_FILE),
+        Err(err) => println!("\nCould not save the game: {:?}", err)
+    }
+
 }
@@ -0,0 +1,229 @@
+use crate::board_types::bitboard::Constants;
+use crate::board_types::chess_move::{Move, parse_coordinates, castling_notation, square_name};
+use crate::enums::{
+    chess_error::ChessError,
+    piece_type::PieceType
+};
+use crate::functions::get_letter;
+use crate::traits::chess_board_contract::ChessBoardContract;
+
+// Check, mate and annotations like "!?" don't change which move is meant, and en passant
+// captures are sometimes marked with "e.p."
+fn strip_suffixes(move_str: &str) -> &str {
+    let move_str = move_str.trim().trim_end_matches(|c| "+#!?".contains(c));
+    move_str.strip_suffix("e.p.").unwrap_or(move_str).trim_end()
+}
+
+fn piece_from_letter(c: char) -> Option<PieceType> {
+    match c {
+        'R' | 'N' | 'B' | 'Q' | 'K' => PieceType::from_char(c),
+        _ => None
+    }
+}
+
+fn find_castling_move(moves: &[Move], move_str: &str) -> Option<Result<Move, ChessError>> {
+    let notation = match move_str.replace('0', "O").as_str() {
+        "O-O" => "O-O",
+        "O-O-O" => "O-O-O",
+        _ => return None
+    };
+
+    Some(moves.iter()
+              .copied()
+              .find(|mov| mov.is_castling() && castling_notation(*mov) == notation)
+              .ok_or(ChessError::InvalidMove))
+}
+
+// "e2e4", "e2 e4" or "e7e8q". Promotions always have the piece
+pub fn move_from_uci<T: ChessBoardContract>(board: &T, move_str: &str, constants: &Constants) -> Result<Move, ChessError> {
+    let (from, to, promotion) = parse_coordinates(move_str).ok_or(ChessError::InvalidMoveString)?;
+
+    board.generate_moves(constants)?
+         .into_iter()
+         .find(|mov| mov.source_square() == from && mov.target_square() == to && mov.promotion() == promotion)
+         .ok_or(ChessError::InvalidMove)
+}
+
+// Whatever a player might type: coordinates like "e2e4" or "e2 e4", SAN or long algebraic notation.
+// A promotion without the piece is a promotion to a queen, which is what players almost always want
+pub fn parse_move<T: ChessBoardContract>(board: &T, move_str: &str, constants: &Constants) -> Result<Move, ChessError> {
+    match read_move(board, move_str, constants) {
+        Err(ChessError::InvalidMove) => {
+            let queen_promotion = match parse_coordinates(move_str) {
+                Some(_) => format!("{}q", move_str.trim()),
+                None => format!("{}=Q", strip_suffixes(move_str))
+            };
+            read_move(board, &queen_promotion, constants).map_err(|_| ChessError::InvalidMove)
+        },
+        res => res
+    }
+}
+
+// If neither SAN nor long algebraic notation fits, the error is the one from reading it as SAN
+fn read_move<T: ChessBoardContract>(board: &T, move_str: &str, constants: &Constants) -> Result<Move, ChessError> {
+    match parse_coordinates(move_str) {
+        Some(_) => move_from_uci(board, move_str, constants),
+        None => move_from_san(board, move_str, constants)
+            .or_else(|err| move_from_long_algebraic(board, move_str, constants).map_err(|_| err))
+    }
+}
+
+// "e2-e4", "Ng1-f3", "e5xd6", "e7-e8=Q" or "O-O"
+pub fn move_from_long_algebraic<T: ChessBoardContract>(board: &T, move_str: &str, constants: &Constants) -> Result<Move, ChessError> {
+    let move_str = strip_suffixes(move_str);
+    let moves = board.generate_moves(constants)?;
+
+    if let Some(res) = find_castling_move(&moves, move_str) {
+        return res;
+    }
+
+    let piece = move_str.chars().next().and_then(piece_from_letter);
+    let squares: String = move_str.chars()
+                                  .skip(piece.is_some() as usize)
+                                  .filter(|c| !"-x=".contains(*c))
+                                  .collect();
+
+    let mov = move_from_uci(board, &squares, constants)?;
+    match mov.piece() == piece.unwrap_or(PieceType::Pawn) {
+        true => Ok(mov),
+        false => Err(ChessError::InvalidMove)
+    }
+}
+
+// Standard algebraic notation: the piece, as much of the square it comes from as is needed to
+// tell it apart from the other pieces of the same type that can move there, the square it moves
+// to and + or # if it gives check or mate
+pub fn move_to_san<T: ChessBoardContract>(board: &T, mov: Move, constants: &Constants) -> Result<String, ChessError> {
+    let moves = board.generate_moves(constants)?;
+    if !moves.contains(&mov) {
+        return Err(ChessError::InvalidMove);
+    }
+
+    let mut res = String::with_capacity(8);
+    let (from, to) = (mov.source_square(), mov.target_square());
+
+    if mov.is_castling() {
+        res.push_str(castling_notation(mov));
+    } else {
+        if mov.piece() == PieceType::Pawn {
+            if mov.is_capture() {
+                res.push(get_letter((from & 7) as usize));
+            }
+        } else {
+            res.push(mov.piece().get_char().to_ascii_uppercase());
+
+            let others: Vec<u32> = moves.iter()
+                                        .filter(|other| other.piece() == mov.piece() && other.target_square() == to && other.source_square() != from)
+                                        .map(|other| other.source_square())
+                                        .collect();
+
+            if !others.is_empty() {
+                let from_name = square_name(from);
+                if others.iter().all(|other| other & 7 != from & 7) {
+                    res.push_str(&from_name[..1]);
+                } else if others.iter().all(|other| other >> 3 != from >> 3) {
+                    res.push_str(&from_name[1..]);
+                } else {
+                    res.push_str(&from_name);
+                }
+            }
+        }
+
+        if mov.is_capture() {
+            res.push('x');
+        }
+        res.push_str(&square_name(to));
+
+        if let Some(typ) = mov.promotion() {
+            res.push('=');
+            res.push(typ.get_char().to_ascii_uppercase());
+        }
+    }
+
+    let new_board = board.after_move(mov)?;
+    if new_board.in_check(constants) {
+        match new_board.generate_moves(constants)?.is_empty() {
+            true => res.push('#'),
+            false => res.push('+')
+        }
+    }
+
+    Ok(res)
+}
+
+// A line of moves in SAN, each played after the ones before it
+pub fn line_to_san<T: ChessBoardContract>(board: &T, moves: &[Move], constants: &Constants) -> Result<Vec<String>, ChessError> {
+    let mut board = board.clone();
+    let mut res = Vec::with_capacity(moves.len());
+
+    for mov in moves {
+        res.push(move_to_san(&board, *mov, constants)?);
+        board = board.after_move(*mov)?;
+    }
+
+    Ok(res)
+}
+
+// "Nf3", "exd5", "O-O", "e8=Q+" or "Rad1". Fails with ChessError::AmbiguousMove if more than one
+// legal move fits
+pub fn move_from_san<T: ChessBoardContract>(board: &T, move_str: &str, constants: &Constants) -> Result<Move, ChessError> {
+    let move_str = strip_suffixes(move_str);
+    let moves = board.generate_moves(constants)?;
+
+    if let Some(res) = find_castling_move(&moves, move_str) {
+        return res;
+    }
+
+    let mut characters: Vec<char> = move_str.chars().filter(|c| !"x:-".contains(*c)).collect();
+
+    // "e8=Q", or "e8Q" without the equals sign
+    let mut promotion = None;
+    if characters.len() > 2 && piece_from_letter(characters[characters.len() - 1]).is_some() {
+        promotion = piece_from_letter(characters.pop().unwrap());
+        if characters.last() == Some(&'=') {
+            characters.pop();
+        }
+    }
+
+    let piece = match characters.first().copied().and_then(piece_from_letter) {
+        Some(typ) => {
+            characters.remove(0);
+            typ
+        },
+        None => PieceType::Pawn
+    };
+
+    if characters.len() < 2 || characters.len() > 4 {
+        return Err(ChessError::InvalidMoveString);
+    }
+
+    let square = &characters[characters.len() - 2..];
+    if !('a'..='h').contains(&square[0]) || !('1'..='8').contains(&square[1]) {
+        return Err(ChessError::InvalidMoveString);
+    }
+    let to = (square[0] as u32 - 'a' as u32) + ((square[1] as u32 - '1' as u32) << 3);
+
+    // What is left is the file and/or rank of the square the piece comes from
+    let mut from_letter = None;
+    let mut from_number = None;
+    for c in characters[..characters.len() - 2].iter() {
+        match c {
+            'a'..='h' => from_letter = Some(*c as u32 - 'a' as u32),
+            '1'..='8' => from_number = Some(*c as u32 - '1' as u32),
+            _ => return Err(ChessError::InvalidMoveString)
+        }
+    }
+
+    let candidates: Vec<Move> = moves.into_iter()
+                                     .filter(|mov| mov.piece() == piece && mov.target_square() == to && !mov.is_castling())
+                                     .filter(|mov| mov.promotion() == promotion)
+                                     .filter(|mov| from_letter.is_none_or(|letter| mov.source_square() & 7 == letter))
+                                     .filter(|mov| from_number.is_none_or(|number| mov.source_square() >> 3 == number))
+                                     .collect();
+
+    match candidates.len() {
+        0 => Err(ChessError::InvalidMove),
+        1 => Ok(candidates[0]),
+        _ => Err(ChessError::AmbiguousMove)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::board_types::bitboard::{BitBoard, Constants};
+use crate::board_types::normalboard::NormalBoard;
+use crate::board_types::position::START_FEN;
+use crate::enums::{
+    board_type::BoardType,
+    chess_error::ChessError
+};
+use crate::traits::chess_board_contract::ChessBoardContract;
+
+use std::time::Instant;
+
+// perft <depth> [--normalboard] [fen]
+pub fn perft_command(args: &[String]) {
+    let depth = match args.first().and_then(|depth| depth.parse::<u32>().ok()) {
+        Some(depth) => depth,
+        None => {
+            println!("Usage: perft <depth> [--normalboard] [fen]");
+            return;
+        }
+    };
+
+    let board_type = match args.iter().any(|arg| arg == "--normalboard") {
+        true => BoardType::Normal,
+        false => BoardType::Bit
+    };
+
+    let fen_fields: Vec<&str> = args[1..].iter().filter(|arg| *arg != "--normalboard").map(|arg| arg.as_str()).collect();
+    let fen = match fen_fields.len() {
+        0 => START_FEN.to_string(),
+        _ => fen_fields.join(" ")
+    };
+
+    let constants = Constants::new();
+    let res = match board_type {
+        BoardType::Normal => NormalBoard::from_fen(&fen).and_then(|board| print_divide(&board, depth, &constants)),
+        BoardType::Bit => BitBoard::from_fen(&fen).and_then(|board| print_divide(&board, depth, &constants))
+    };
+
+    if let Err(err) = res {
+        println!("{:?}", err);
+    }
+}
+
+fn print_divide<T: ChessBoardContract>(board: &T, depth: u32, constants: &Constants) -> Result<(), ChessError> {
+    let start_time = Instant::now();
+
+    let mut moves: Vec<(String, u64)> = board.divide(depth, constants)?
+                                              .into_iter()
+                                              .map(|(mov, nodes)| (mov.to_uci(), nodes))
+                                              .collect();
+    moves.sort();
+
+    for (mov_str, nodes) in moves.iter() {
+        println!("{}: {}", mov_str, nodes);
+    }
+
+    let nodes: u64 = moves.iter().map(|(_, nodes)| nodes).sum();
+    let millis = start_time.elapsed().as_millis().max(1) as u64;
+    println!("\nNodes searched: {}", nodes);
+    println!("Time: {} ms ({} nodes per second)", millis, nodes * 1000 / millis);
+
+    Ok(())
+}
@@ -0,0 +1,482 @@
+use crate::board_types::bitboard::{BitBoard, Constants};
+use crate::enums::chess_error::ChessError;
+use crate::notation::move_from_san;
+use crate::evaluation_functions::score::mate_in_moves;
+use crate::traits::chess_board_contract::ChessBoardContract;
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// The PGN standard allows lines of up to 80 characters
+const MAX_LINE_LENGTH: usize = 79;
+
+pub const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+const RESULTS: [&str; 4] = ["1-0", "0-1", "1/2-1/2", "*"];
+
+// The move annotations that have a NAG of their own, "!" is $1 and so on
+const SUFFIX_ANNOTATIONS: [&str; 6] = ["!", "?", "!!", "??", "!?", "?!"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PgnMove {
+    pub san: String,
+    pub comment: Option<String>,
+    // Numeric annotation glyphs, like $1 for a good move
+    pub nags: Vec<u8>,
+    // Other moves that could have been played instead of this one, each followed by its own line
+    pub variations: Vec<Vec<PgnMove>>
+}
+
+impl PgnMove {
+    pub fn new(san: String, comment: Option<String>) -> Self {
+        Self {
+            san,
+            comment,
+            nags: Vec::new(),
+            variations: Vec::new()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PgnGame {
+    // In the order they are written, which starts with the Seven Tag Roster
+    pub tags: Vec<(String, String)>,
+    // A comment before the first move
+    pub comment: Option<String>,
+    pub moves: Vec<PgnMove>,
+    // "1-0", "0-1", "1/2-1/2" or "*" if the game has not ended
+    pub result: String
+}
+
+// Where replaying a game went wrong. The ply is the number of the half-move that failed,
+// starting at 1, or 0 if the starting position could not be set up
+#[derive(Clone, Debug, PartialEq)]
+pub struct PgnError {
+    pub ply: usize,
+    pub san: String,
+    pub error: ChessError
+}
+
+enum PgnToken {
+    Tag(String, String),
+    Comment(String),
+    Nag(u8),
+    VariationStart,
+    VariationEnd,
+    Result(String),
+    Move(String)
+}
+
+impl PgnGame {
+    // Tags that are not known yet are "?", as the standard asks
+    pub fn new() -> Self {
+        let tags = SEVEN_TAG_ROSTER.iter().map(|name| {
+            let value = match *name {
+                "Date" => "????.??.??",
+                "Result" => "*",
+                _ => "?"
+            };
+            (name.to_string(), value.to_string())
+        }).collect();
+
+        Self {
+            tags,
+            comment: None,
+            moves: Vec::new(),
+            result: "*".to_string()
+        }
+    }
+
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old_value)) => *old_value = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string()))
+        }
+    }
+
+    // The Result tag is always written with the result of the moves, and the comments only if asked for
+    pub fn to_pgn(&self, comments: bool) -> String {
+        let mut res = String::new();
+
+        for (name, value) in self.tags.iter() {
+            let value = match name.as_str() {
+                "Result" => &self.result,
+                _ => value
+            };
+            res.push_str(&format!("[{} \"{}\"]\n", name, value.replace('\\', "\\\\").replace('"', "\\\"")));
+        }
+        res.push('\n');
+
+        // A game set up from a FEN can start with black to move, and at any move number
+        let fen_fields: Vec<&str> = self.get_tag("FEN").unwrap_or("").split_whitespace().collect();
+        let white_to_move = fen_fields.get(1) != Some(&"b");
+        let fullmove_number: u32 = fen_fields.get(5).and_then(|number| number.parse().ok()).unwrap_or(1);
+
+        let mut tokens: Vec<String> = Vec::with_capacity(self.moves.len() * 2 + 2);
+        if let Some(comment) = self.comment.as_ref().filter(|_| comments) {
+            tokens.push(comment_token(comment));
+        }
+        write_moves(&self.moves, white_to_move, fullmove_number, comments, &mut tokens);
+        tokens.push(self.result.clone());
+
+        let mut line_length = 0;
+        for word in tokens.iter().flat_map(|token| token.split_whitespace()) {
+            if line_length > 0 && line_length + 1 + word.len() > MAX_LINE_LENGTH {
+                res.push('\n');
+                line_length = 0;
+            } else if line_length > 0 {
+                res.push(' ');
+                line_length += 1;
+            }
+
+            res.push_str(word);
+            line_length += word.len();
+        }
+        res.push('\n');
+
+        res
+    }
+
+    // Plays the moves from the starting position of the game, or the FEN tag if there is one, and
+    // returns every position on the way, starting with the first one. Variations are not played
+    pub fn replay<T: ChessBoardContract>(&self, constants: &Constants) -> Result<Vec<T>, PgnError> {
+        let start_board = match self.get_tag("FEN") {
+            Some(fen) => T::from_fen(fen),
+            None => Ok(T::new_board())
+        }.map_err(|error| PgnError { ply: 0, san: String::new(), error })?;
+
+        let mut positions = Vec::with_capacity(self.moves.len() + 1);
+        positions.push(start_board);
+
+        for (i, mov) in self.moves.iter().enumerate() {
+            let board = &positions[i];
+            let new_board = move_from_san(board, &mov.san, constants)
+                .and_then(|parsed_move| board.after_move(parsed_move))
+                .map_err(|error| PgnError { ply: i + 1, san: mov.san.clone(), error })?;
+
+            positions.push(new_board);
+        }
+
+        Ok(positions)
+    }
+
+    pub fn write_to_file(&self, path: &str, comments: bool) -> Result<(), ChessError> {
+        std::fs::write(path, self.to_pgn(comments)).map_err(|_| ChessError::FileError)
+    }
+}
+
+fn comment_token(comment: &str) -> String {
+    // A comment ends at the first closing brace, so it can't contain one
+    format!("{{{}}}", comment.replace('}', ")"))
+}
+
+// Adds the move numbers, moves, annotations and variations to the tokens of the movetext
+fn write_moves(moves: &[PgnMove], mut white_to_move: bool, mut fullmove_number: u32, comments: bool, tokens: &mut Vec<String>) {
+    let mut needs_number = true;
+
+    for mov in moves.iter() {
+        match white_to_move {
+            true => tokens.push(format!("{}.", fullmove_number)),
+            // Black's moves only get a number where the moves are interrupted
+            false if needs_number => tokens.push(format!("{}...", fullmove_number)),
+            false => ()
+        }
+        tokens.push(mov.san.clone());
+        needs_number = false;
+
+        for nag in mov.nags.iter() {
+            tokens.push(format!("${}", nag));
+        }
+
+        if let Some(comment) = mov.comment.as_ref().filter(|_| comments) {
+            tokens.push(comment_token(comment));
+            needs_number = true;
+        }
+
+        // A variation starts from the same position as the move it replaces
+        for variation in mov.variations.iter().filter(|variation| !variation.is_empty()) {
+            let start = tokens.len();
+            write_moves(variation, white_to_move, fullmove_number, comments, tokens);
+
+            tokens[start].insert(0, '(');
+            tokens.last_mut().unwrap().push(')');
+            needs_number = true;
+        }
+
+        if !white_to_move {
+            fullmove_number += 1;
+        }
+        white_to_move = !white_to_move;
+    }
+}
+
+// Reads every game in the text. Moves are only checked to look like moves, PgnGame::replay
+// checks if they can be played
+pub fn parse_pgn(text: &str) -> Result<Vec<PgnGame>, ChessError> {
+    let mut games = Vec::new();
+    let mut game = PgnGame { tags: Vec::new(), comment: None, moves: Vec::new(), result: "*".to_string() };
+    // The move lists of the variations that are still open, on top of the moves of the game
+    let mut lines: Vec<Vec<PgnMove>> = vec![Vec::new()];
+    let mut has_result = false;
+
+    for token in tokenize_pgn(text)? {
+        // A game without a result ends where the tags of the next one start
+        let game_started = !lines[0].is_empty() || has_result;
+        if (game_started && matches!(token, PgnToken::Tag(..))) || has_result {
+            if lines.len() > 1 {
+                return Err(ChessError::InvalidPgn);
+            }
+
+            game.moves = lines.pop().unwrap();
+            games.push(std::mem::replace(&mut game, PgnGame { tags: Vec::new(), comment: None, moves: Vec::new(), result: "*".to_string() }));
+            lines.push(Vec::new());
+            has_result = false;
+        }
+
+        let line = lines.last_mut().unwrap();
+        match token {
+            PgnToken::Tag(name, value) => {
+                if name == "Result" {
+                    game.result = value.clone();
+                }
+                game.tags.push((name, value));
+            },
+            PgnToken::Move(san) => line.push(PgnMove::new(san, None)),
+            PgnToken::Nag(nag) => line.last_mut().ok_or(ChessError::InvalidPgn)?.nags.push(nag),
+            PgnToken::Comment(comment) => {
+                let depth = lines.len();
+                let target = match lines.last_mut().unwrap().last_mut() {
+                    Some(mov) => &mut mov.comment,
+                    None if depth == 1 => &mut game.comment,
+                    // Comments at the start of a variation have nothing to belong to
+                    None => continue
+                };
+
+                *target = Some(match target.take() {
+                    Some(old_comment) => format!("{} {}", old_comment, comment),
+                    None => comment
+                });
+            },
+            PgnToken::VariationStart => {
+                if line.is_empty() {
+                    return Err(ChessError::InvalidPgn);
+                }
+                lines.push(Vec::new());
+            },
+            PgnToken::VariationEnd => {
+                if lines.len() == 1 {
+                    return Err(ChessError::InvalidPgn);
+                }
+                let variation = lines.pop().unwrap();
+                lines.last_mut().unwrap().last_mut().unwrap().variations.push(variation);
+            },
+            PgnToken::Result(result) => {
+                if lines.len() > 1 {
+                    return Err(ChessError::InvalidPgn);
+                }
+                game.result = result;
+                has_result = true;
+            }
+        }
+    }
+
+    if lines.len() > 1 {
+        return Err(ChessError::InvalidPgn);
+    }
+    if has_result || !game.tags.is_empty() || !lines[0].is_empty() {
+        game.moves = lines.pop().unwrap();
+        games.push(game);
+    }
+
+    Ok(games)
+}
+
+fn tokenize_pgn(text: &str) -> Result<Vec<PgnToken>, ChessError> {
+    let mut tokens = Vec::new();
+    let mut characters = text.chars().peekable();
+    let mut line_start = true;
+
+    while let Some(c) = characters.next() {
+        let at_line_start = line_start;
+        line_start = c == '\n';
+
+        match c {
+            // Lines starting with % are ignored, like comments until the end of the line
+            '%' if at_line_start => {
+                characters.by_ref().find(|c| *c == '\n');
+                line_start = true;
+            },
+            ';' => {
+                let comment: String = characters.by_ref().take_while(|c| *c != '\n').collect();
+                tokens.push(PgnToken::Comment(comment.trim().to_string()));
+                line_start = true;
+            },
+            '{' => {
+                let mut comment = String::new();
+                loop {
+                    match characters.next() {
+                        Some('}') => break,
+                        Some(c) => comment.push(c),
+                        None => return Err(ChessError::InvalidPgn)
+                    }
+                }
+                tokens.push(PgnToken::Comment(comment.split_whitespace().collect::<Vec<&str>>().join(" ")));
+            },
+            '[' => {
+                // The value can contain ']' as well, so the tag ends at the first one after the quoted value
+                let mut tag = String::new();
+                let mut in_value = false;
+                loop {
+                    match characters.next() {
+                        Some(']') if !in_value => break,
+                        Some('\\') if in_value => {
+                            tag.push('\\');
+                            tag.push(characters.next().ok_or(ChessError::InvalidPgn)?);
+                        },
+                        Some(c) => {
+                            in_value ^= c == '"';
+                            tag.push(c);
+                        },
+                        None => return Err(ChessError::InvalidPgn)
+                    }
+                }
+                tokens.push(parse_tag(&tag).ok_or(ChessError::InvalidPgn)?);
+            },
+            '(' => tokens.push(PgnToken::VariationStart),
+            ')' => tokens.push(PgnToken::VariationEnd),
+            '$' => {
+                let mut number = String::new();
+                while let Some(digit) = characters.next_if(|c| c.is_ascii_digit()) {
+                    number.push(digit);
+                }
+                tokens.push(PgnToken::Nag(number.parse().map_err(|_| ChessError::InvalidPgn)?));
+            },
+            c if c.is_whitespace() || c == '.' => (),
+            c => {
+                // Dots end move numbers, but can be part of a move like "exd6e.p."
+                let dots = c.is_alphabetic();
+                let mut symbol = c.to_string();
+                while let Some(c) = characters.next_if(|c| c.is_alphanumeric() || "_+#=:-/!?".contains(*c) || (dots && *c == '.')) {
+                    symbol.push(c);
+                }
+                tokens.extend(parse_symbol(&symbol)?);
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+// The inside of '[Event "Casual game"]'
+fn parse_tag(tag: &str) -> Option<PgnToken> {
+    let (name, value) = tag.trim().split_once(char::is_whitespace)?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    // The only escapes are for quotes and backslashes
+    let mut unescaped = String::with_capacity(value.len());
+    let mut characters = value.chars();
+    while let Some(c) = characters.next() {
+        match c {
+            '\\' => unescaped.push(characters.next()?),
+            c => unescaped.push(c)
+        }
+    }
+
+    Some(PgnToken::Tag(name.to_string(), unescaped))
+}
+
+// A result, a move number or a move with "!" or "?" annotations
+fn parse_symbol(symbol: &str) -> Result<Vec<PgnToken>, ChessError> {
+    if RESULTS.contains(&symbol) {
+        return Ok(vec![PgnToken::Result(symbol.to_string())]);
+    }
+
+    // Move numbers are ignored, as the moves are counted anyway. Other symbols starting with a
+    // digit are moves, like castling written as "0-0". En passant captures can be marked with a
+    // separate "e.p.", which doesn't change the move either
+    if symbol.chars().all(|c| c.is_ascii_digit()) || symbol == "e.p." {
+        return Ok(Vec::new());
+    }
+
+    let san = symbol.trim_end_matches(['!', '?']);
+    let annotation = &symbol[san.len()..];
+
+    let mut tokens = vec![PgnToken::Move(san.to_string())];
+    if !annotation.is_empty() {
+        let nag = SUFFIX_ANNOTATIONS.iter().position(|suffix| *suffix == annotation).ok_or(ChessError::InvalidPgn)?;
+        tokens.push(PgnToken::Nag(nag as u8 + 1));
+    }
+
+    Ok(tokens)
+}
+
+// pgn <file>: replays the games in the file and prints where they end, or where they go wrong
+pub fn pgn_command(args: &[String]) {
+    let text = match args.first().map(std::fs::read_to_string) {
+        Some(Ok(text)) => text,
+        Some(Err(_)) => {
+            println!("{:?}", ChessError::FileError);
+            return;
+        },
+        None => {
+            println!("Usage: pgn <file>");
+            return;
+        }
+    };
+
+    let games = match parse_pgn(&text) {
+        Ok(games) => games,
+        Err(err) => {
+            println!("{:?}", err);
+            return;
+        }
+    };
+
+    let constants = Constants::new();
+    for (i, game) in games.iter().enumerate() {
+        let players = format!("{} - {}", game.get_tag("White").unwrap_or("?"), game.get_tag("Black").unwrap_or("?"));
+
+        match game.replay::<BitBoard>(&constants) {
+            Ok(positions) => println!("Game {} ({}): {} plies, {}, final position {}", i + 1, players, game.moves.len(), game.result, positions[positions.len() - 1].to_fen()),
+            Err(err) => println!("Game {} ({}): {:?} at ply {} ({})", i + 1, players, err.error, err.ply, err.san)
+        }
+    }
+}
+
+// The date in the "YYYY.MM.DD" format of the Date tag
+pub fn date_today() -> String {
+    let days = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or(Duration::ZERO).as_secs() as i64 / 86400;
+
+    // Converts days since 1970-01-01 to a date in the proleptic Gregorian calendar, by counting
+    // in eras of 400 years that start on the first of March
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    format!("{:04}.{:02}.{:02}", year, month, day)
+}
+
+// The score for the %eval command in comments, in pawns or "#<moves>" for a mate
+pub fn eval_string(score: i32) -> String {
+    match mate_in_moves(score) {
+        Some(moves) => format!("#{}", moves),
+        None => format!("{:.2}", score as f32 / 100.)
+    }
+}
+
+// "h:mm:ss", as used by the %emt and %clk commands in comments
+pub fn clock_string(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
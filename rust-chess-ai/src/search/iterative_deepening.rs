@@ -0,0 +1,219 @@
+use crate::board_types::bitboard::Constants;
+use crate::traits::chess_board_contract::ChessBoardContract;
+use crate::search::transposition_table::TranspositionTable;
+use crate::search::search_options::SearchOptions;
+use crate::search::search_threads::SearchThreads;
+use crate::search::move_ordering::MoveOrdering;
+use crate::turn_functions::minimax_move::{search, search_window, SearchResult};
+use crate::EvaluationFunction;
+use crate::evaluation_functions::score::{is_mate_score, MATE_SCORE, INFINITY};
+
+use crate::enums::chess_error::ChessError;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+pub const MAX_DEPTH: i32 = 64;
+// Used to split the remaining time when the number of moves until the next time control is unknown
+const DEFAULT_MOVES_TO_GO: u32 = 30;
+// How far from the score of the last iteration the next one looks at first, in centipawns. The
+// window grows this many times over every time the score falls outside of it
+const ASPIRATION_WINDOW: i32 = 50;
+const ASPIRATION_GROWTH: i32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeControl {
+    // Always searches to the given depth, however long it takes
+    Depth(i32),
+    // Searches as deep as possible in the given time for every move
+    MoveTime(Duration),
+    // A chess clock starting at time, with increment added after every move
+    Clock {
+        time: Duration,
+        increment: Duration
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchLimits {
+    pub depth: i32,
+    pub time: Option<Duration>
+}
+
+// The position to search and everything the search needs for it. The searches in
+// minimax_move search to limits.depth and leave the time to whoever sets stop. history has the
+// hashes of the positions of the game before the board, so the search can tell repetitions
+pub struct SearchParams<'a, T> {
+    pub board: &'a T,
+    pub history: &'a [u64],
+    pub limits: SearchLimits,
+    pub eval_func: EvaluationFunction<T>,
+    pub constants: &'a Constants,
+    pub options: &'a SearchOptions,
+    pub tt: &'a Arc<TranspositionTable>,
+    pub stop: &'a AtomicBool
+}
+
+impl<T> SearchParams<'_, T> {
+    // The same search to another depth
+    pub fn with_depth(&self, depth: i32) -> Self {
+        Self {
+            limits: SearchLimits { depth, ..self.limits },
+            ..*self
+        }
+    }
+}
+
+// How much of the remaining clock time to spend on one move
+pub fn time_budget(time_left: Duration, increment: Duration, moves_to_go: Option<u32>) -> Duration {
+    let moves_to_go = moves_to_go.unwrap_or(DEFAULT_MOVES_TO_GO).max(1);
+    (time_left / moves_to_go + increment / 2).min(time_left / 2)
+}
+
+// Searches depth 1, 2, 3... until the depth limit is reached, the time runs out or stop is set,
+// and returns the result of the last iteration that finished. on_iteration is called after every
+// finished iteration, with the nodes and cutoffs counted over all iterations so far. With
+// helpers, up to options.threads - 1 of them search the same position until this search is done
+pub fn iterative_deepening<T, F>(params: &SearchParams<T>,
+                                 helpers: Option<&SearchThreads>,
+                                 mut on_iteration: F) -> Result<Option<SearchResult>, ChessError>
+    where T: 'static + ChessBoardContract + Send + Sync,
+          F: FnMut(&SearchResult, Duration) {
+
+    let SearchParams { limits, options, tt, stop, .. } = *params;
+    let start_time = Instant::now();
+    tt.new_search();
+
+    // The helpers only stop when this search is done, and dropping the last sender tells that
+    // they all have
+    let helper_stop = Arc::new(AtomicBool::new(false));
+    let helper_nodes = Arc::new(AtomicU64::new(0));
+    let (helpers_done_sender, helpers_done_receiver) = mpsc::channel::<()>();
+
+    if let Some(helpers) = helpers.filter(|_| options.threads > 1) {
+        let (board, history, options, eval_func) = (params.board.clone(), params.history.to_vec(), *options, params.eval_func);
+        let (tt, helper_stop, helper_nodes) = (tt.clone(), helper_stop.clone(), helper_nodes.clone());
+        let helpers_done_sender = helpers_done_sender.clone();
+        let helper_count = options.threads - 1;
+
+        helpers.run(move |i, constants| {
+            // Keeps the sender until the job is done
+            let _done = &helpers_done_sender;
+            if i < helper_count {
+                let params = SearchParams {
+                    board: &board,
+                    history: &history,
+                    limits,
+                    eval_func,
+                    constants,
+                    options: &options,
+                    tt: &tt,
+                    stop: &helper_stop
+                };
+                helper_search(&params, i, &helper_nodes);
+            }
+        });
+    }
+    drop(helpers_done_sender);
+
+    let res = std::thread::scope(|s| {
+        // Dropping the sender when the search is done wakes the timer up without setting stop
+        let (done_sender, done_receiver) = mpsc::channel::<()>();
+        if let Some(time) = limits.time {
+            s.spawn(move || {
+                if let Err(RecvTimeoutError::Timeout) = done_receiver.recv_timeout(time) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+            });
+        }
+
+        let mut last_result: Option<SearchResult> = None;
+        let mut total_nodes = 0;
+        let mut total_cutoffs = 0;
+        let mut total_first_move_cutoffs = 0;
+
+        // Every search starts with new killer moves and history, which are
+        // then kept from one iteration to the next
+        let mut ordering = MoveOrdering::new();
+
+        'deepening: for depth in 1..=limits.depth {
+            ordering.age();
+
+            // The score rarely changes much between iterations, and a narrow window around the
+            // last one prunes more. A score outside of it is only a bound, so the window is
+            // widened on that side and the iteration searched again
+            let mut delta = ASPIRATION_WINDOW;
+            let (mut alpha, mut beta) = match &last_result {
+                Some(last) if options.alpha_beta_pruning && !is_mate_score(last.score) => (last.score - delta, last.score + delta),
+                _ => (-INFINITY, INFINITY)
+            };
+
+            let mut res = loop {
+                let res = match search_window(&params.with_depth(depth), alpha, beta, &mut ordering) {
+                    Ok(res) => res,
+                    Err(ChessError::SearchStopped) => break 'deepening,
+                    Err(err) => return Err(err)
+                };
+
+                total_nodes += res.nodes;
+                total_cutoffs += res.cutoffs;
+                total_first_move_cutoffs += res.first_move_cutoffs;
+
+                delta = delta.saturating_mul(ASPIRATION_GROWTH);
+                if res.score <= alpha {
+                    alpha = res.score.saturating_sub(delta).max(-INFINITY);
+                } else if res.score >= beta {
+                    beta = res.score.saturating_add(delta);
+                } else {
+                    break res;
+                }
+            };
+
+            res.nodes = total_nodes + helper_nodes.load(Ordering::Relaxed);
+            res.cutoffs = total_cutoffs;
+            res.first_move_cutoffs = total_first_move_cutoffs;
+
+            let elapsed = start_time.elapsed();
+            on_iteration(&res, elapsed);
+
+            // A deeper search can't find a shorter mate than one that was found within this depth
+            let mate_found = is_mate_score(res.score) && MATE_SCORE - res.score.abs() <= depth;
+            last_result = Some(res);
+            if mate_found {
+                break;
+            }
+
+            // The next iteration takes several times as long, so there is no point in starting it
+            // if half of the time is already used
+            if let Some(time) = limits.time {
+                if elapsed * 2 > time {
+                    break;
+                }
+            }
+        }
+
+        drop(done_sender);
+        Ok(last_result)
+    });
+
+    helper_stop.store(true, Ordering::Relaxed);
+    let _ = helpers_done_receiver.recv();
+
+    res
+}
+
+// A helper thread of the Lazy SMP search. It searches deeper and deeper like the main thread, but
+// only for the transposition table. Every other helper starts one move deeper, so the threads
+// don't all search the same depth, and the nodes of every finished iteration are added to nodes
+fn helper_search<T: ChessBoardContract>(params: &SearchParams<T>, index: usize, nodes: &AtomicU64) {
+    let mut ordering = MoveOrdering::new();
+    for depth in (1 + (index % 2) as i32)..=params.limits.depth {
+        ordering.age();
+        match search(&params.with_depth(depth), &mut ordering) {
+            Ok(res) => nodes.fetch_add(res.nodes, Ordering::Relaxed),
+            Err(_) => break
+        };
+    }
+}
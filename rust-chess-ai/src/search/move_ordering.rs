@@ -0,0 +1,112 @@
+use crate::board_types::chess_move::Move;
+use crate::enums::piece_color::PieceColor;
+
+use std::cmp::Reverse;
+
+// Moves are searched from the highest score to the lowest: the hash move, captures and
+// promotions, the killer moves and then the other moves by their history
+const HASH_MOVE_SCORE: i32 = 1 << 30;
+const CAPTURE_SCORE: i32 = 1 << 28;
+const KILLER_SCORE: i32 = 1 << 27;
+// History scores are halved when one gets this high, so they stay below the killer moves and
+// recent cutoffs count more than old ones
+const MAX_HISTORY: i32 = 1 << 20;
+
+// Killer moves are only kept this many plies deep
+const MAX_KILLER_PLY: usize = 128;
+
+// Most valuable victim, least valuable attacker: taking a queen with a pawn comes first and
+// taking a pawn with the king last. Indexed by PieceType
+const MVV_LVA_VALUES: [i32; 6] = [1, 5, 3, 3, 9, 20];
+
+// The killer moves and history of one thread's search. Both are quiet moves that caused a cutoff
+// earlier, in the same ply for the killer moves and anywhere in the tree for the history
+pub struct MoveOrdering {
+    killers: Vec<[Option<Move>; 2]>,
+    // Indexed [color][from][to]
+    history: Box<[[[i32; 64]; 64]; 2]>
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MoveOrdering {
+    pub fn new() -> Self {
+        Self {
+            killers: vec![[None; 2]; MAX_KILLER_PLY],
+            history: Box::new([[[0; 64]; 64]; 2])
+        }
+    }
+
+    // Sorts the moves so the ones that are most likely to cause a cutoff come first. Without
+    // move_ordering only the hash move is moved to the front
+    pub fn order_moves(&self, moves: &mut [Move], hash_move: Option<u16>, ply: i32, color: PieceColor, move_ordering: bool) {
+        if !move_ordering {
+            if let Some(i) = moves.iter().position(|mov| Some(mov.key()) == hash_move) {
+                moves[..=i].rotate_right(1);
+            }
+            return;
+        }
+
+        let killers = self.killers.get(ply as usize).copied().unwrap_or([None; 2]);
+        moves.sort_by_cached_key(|mov| {
+            let score = if Some(mov.key()) == hash_move {
+                HASH_MOVE_SCORE
+            } else if is_tactical(*mov) {
+                CAPTURE_SCORE + mvv_lva(*mov)
+            } else if killers[0] == Some(*mov) {
+                KILLER_SCORE + 1
+            } else if killers[1] == Some(*mov) {
+                KILLER_SCORE
+            } else {
+                self.history[color as usize][mov.source_square() as usize][mov.target_square() as usize]
+            };
+
+            Reverse(score)
+        });
+    }
+
+    // Halves the history. Also done before every deeper iteration, so its cutoffs count more
+    // than the ones of the shallower iterations before it
+    pub fn age(&mut self) {
+        for value in self.history.iter_mut().flatten().flatten() {
+            *value /= 2;
+        }
+    }
+
+    // Only called for quiet moves, as captures are already searched first
+    pub fn add_cutoff(&mut self, mov: Move, ply: i32, depth: i32, color: PieceColor) {
+        if let Some(killers) = self.killers.get_mut(ply as usize) {
+            if killers[0] != Some(mov) {
+                killers[1] = killers[0];
+                killers[0] = Some(mov);
+            }
+        }
+
+        let history = &mut self.history[color as usize][mov.source_square() as usize][mov.target_square() as usize];
+        *history += depth * depth;
+
+        if *history > MAX_HISTORY {
+            self.age();
+        }
+    }
+}
+
+// Captures and promotions, which change the material
+pub fn is_tactical(mov: Move) -> bool {
+    mov.is_capture() || mov.promotion().is_some()
+}
+
+pub fn order_captures(moves: &mut [Move]) {
+    moves.sort_by_cached_key(|mov| Reverse(mvv_lva(*mov)));
+}
+
+fn mvv_lva(mov: Move) -> i32 {
+    let victim = mov.captured().map(|typ| MVV_LVA_VALUES[typ as usize]).unwrap_or(0);
+    let promotion = mov.promotion().map(|typ| MVV_LVA_VALUES[typ as usize]).unwrap_or(0);
+
+    (victim + promotion) * 32 - MVV_LVA_VALUES[mov.piece() as usize]
+}
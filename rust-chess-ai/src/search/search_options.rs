@@ -0,0 +1,53 @@
+// The parts of the search that can be turned off, to compare the results and node counts with
+// and without them
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchOptions {
+    pub alpha_beta_pruning: bool,
+    // The search runs on this many threads: the main one and helpers searching the same position
+    // (Lazy SMP). The helpers only fill the shared transposition table, which the main thread
+    // then finds its positions in
+    pub threads: usize,
+    // Searches the hash move, captures by MVV-LVA, killer moves and quiet moves by their history
+    // first, instead of the moves in the order they are generated
+    pub move_ordering: bool,
+    // Lets the other player move twice in a row with a shallower search. If that still isn't
+    // enough for them, the position is pruned. Never when in check or with only pawns left, where
+    // passing could be better than any move (zugzwang)
+    pub null_move_pruning: bool,
+    // Searches quiet moves late in the move order less deep, unless they turn out better than
+    // the moves before them
+    pub late_move_reductions: bool,
+    // Skips quiet moves right before the leaves when the position is too far below alpha for
+    // them to make up for it
+    pub futility_pruning: bool,
+    // Searches moves that give check one move deeper
+    pub check_extensions: bool
+}
+
+impl SearchOptions {
+    // Searches every line to the same depth and only prunes what can't change the score, so the
+    // result is the same as without alpha-beta pruning
+    pub fn full_width() -> Self {
+        Self {
+            null_move_pruning: false,
+            late_move_reductions: false,
+            futility_pruning: false,
+            check_extensions: false,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            alpha_beta_pruning: true,
+            threads: 1,
+            move_ordering: true,
+            null_move_pruning: true,
+            late_move_reductions: true,
+            futility_pruning: true,
+            check_extensions: true
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::board_types::bitboard::Constants;
+
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+
+type Job = Box<dyn FnOnce(&Constants) + Send>;
+
+// Helper threads that are started once and then wait for work, so searching a move doesn't
+// start new ones every time. The jobs can't borrow from whoever hands them out, as the threads
+// live longer, so the threads keep a reference to the constants
+pub struct SearchThreads {
+    senders: Vec<mpsc::Sender<Job>>,
+    handles: Vec<JoinHandle<()>>
+}
+
+impl SearchThreads {
+    pub fn new(count: usize, constants: Arc<Constants>) -> Self {
+        let (senders, handles) = (0..count).map(|_| {
+            let (sender, receiver) = mpsc::channel::<Job>();
+            let constants = constants.clone();
+
+            // Runs until the sender is dropped
+            let handle = std::thread::spawn(move || {
+                for job in receiver {
+                    job(&constants);
+                }
+            });
+
+            (sender, handle)
+        }).unzip();
+
+        Self {
+            senders,
+            handles
+        }
+    }
+
+    // Gives every thread the job, together with the index of the thread
+    pub fn run<F>(&self, job: F)
+        where F: Fn(usize, &Constants) + Send + Clone + 'static {
+
+        for (i, sender) in self.senders.iter().enumerate() {
+            let job = job.clone();
+            let _ = sender.send(Box::new(move |constants: &Constants| job(i, constants)));
+        }
+    }
+}
+
+impl Drop for SearchThreads {
+    fn drop(&mut self) {
+        self.senders.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::sync::atomic::{AtomicU8, AtomicU64, Ordering};
+
+// Each bucket holds one depth-preferred and one always-replace entry
+const ENTRIES_PER_BUCKET: usize = 2;
+const ENTRY_SIZE: usize = 16;
+// Move keys only use the lower 15 bits, so this is never a real move
+const NO_MOVE: u64 = 0xFFFF;
+const GENERATION_MASK: u8 = 0x3F;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Bound {
+    Exact = 0,
+    // The real score is at least the stored score
+    Lower = 1,
+    // The real score is at most the stored score
+    Upper = 2
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TTEntry {
+    pub depth: i32,
+    pub score: i32,
+    pub bound: Bound,
+    // Move::key of the best move
+    pub best_move: Option<u16>,
+    generation: u8
+}
+
+// The key is stored xored with the data, so an entry torn by two threads writing
+// at the same time fails the key check instead of returning mixed data
+struct Entry {
+    key: AtomicU64,
+    data: AtomicU64
+}
+
+pub struct TranspositionTable {
+    entries: Vec<Entry>,
+    bucket_mask: usize,
+    generation: AtomicU8
+}
+
+impl TranspositionTable {
+    // The number of buckets is rounded down to a power of two that fits in size_mb
+    pub fn new(size_mb: usize) -> Self {
+        let max_buckets = (size_mb * 1024 * 1024 / (ENTRY_SIZE * ENTRIES_PER_BUCKET)).max(1);
+        let buckets = 1 << (usize::BITS - 1 - max_buckets.leading_zeros());
+
+        Self {
+            entries: (0..buckets * ENTRIES_PER_BUCKET).map(|_| Entry {
+                key: AtomicU64::new(0),
+                data: AtomicU64::new(0)
+            }).collect(),
+            bucket_mask: buckets - 1,
+            generation: AtomicU8::new(0)
+        }
+    }
+
+    pub fn size_mb(&self) -> usize {
+        self.entries.len() * ENTRY_SIZE / (1024 * 1024)
+    }
+
+    // Called at the start of every search, so entries from earlier searches get replaced first
+    pub fn new_search(&self) {
+        let generation = self.generation.load(Ordering::Relaxed);
+        self.generation.store((generation + 1) & GENERATION_MASK, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        for entry in self.entries.iter() {
+            entry.key.store(0, Ordering::Relaxed);
+            entry.data.store(0, Ordering::Relaxed);
+        }
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<TTEntry> {
+        let bucket = self.bucket_index(hash);
+
+        self.entries[bucket..bucket + ENTRIES_PER_BUCKET].iter().find_map(|entry| {
+            let data = entry.data.load(Ordering::Relaxed);
+            match entry.key.load(Ordering::Relaxed) ^ data == hash && data != 0 {
+                true => Some(unpack(data)),
+                false => None
+            }
+        })
+    }
+
+    // Searches only store nodes with depth >= 1, so data that is all zeros marks an empty entry
+    pub fn store(&self, hash: u64, depth: i32, score: i32, bound: Bound, best_move: Option<u16>) {
+        let bucket = self.bucket_index(hash);
+        let generation = self.generation.load(Ordering::Relaxed);
+
+        let depth_preferred = &self.entries[bucket];
+        let old_data = depth_preferred.data.load(Ordering::Relaxed);
+        let old_hash = depth_preferred.key.load(Ordering::Relaxed) ^ old_data;
+        let old = unpack(old_data);
+
+        let entry = match old_data == 0 || old_hash == hash || old.generation != generation || depth >= old.depth {
+            true => depth_preferred,
+            false => &self.entries[bucket + 1]
+        };
+
+        let data = pack(depth, score, bound, best_move, generation);
+        entry.key.store(hash ^ data, Ordering::Relaxed);
+        entry.data.store(data, Ordering::Relaxed);
+    }
+
+    fn bucket_index(&self, hash: u64) -> usize {
+        (hash as usize & self.bucket_mask) * ENTRIES_PER_BUCKET
+    }
+}
+
+// Bits 0-31 score, 32-39 depth, 40-41 bound, 42-47 generation, 48-63 best move
+fn pack(depth: i32, score: i32, bound: Bound, best_move: Option<u16>, generation: u8) -> u64 {
+    let best_move = best_move.map(|key| key as u64).unwrap_or(NO_MOVE);
+
+    (score as u32 as u64)
+        | ((depth.clamp(0, u8::MAX as i32) as u64) << 32)
+        | ((bound as u64) << 40)
+        | (((generation & GENERATION_MASK) as u64) << 42)
+        | (best_move << 48)
+}
+
+fn unpack(data: u64) -> TTEntry {
+    let best_move = data >> 48;
+
+    TTEntry {
+        depth: ((data >> 32) & 0xFF) as i32,
+        score: data as u32 as i32,
+        bound: match (data >> 40) & 3 {
+            0 => Bound::Exact,
+            1 => Bound::Lower,
+            _ => Bound::Upper
+        },
+        best_move: match best_move {
+            NO_MOVE => None,
+            _ => Some(best_move as u16)
+        },
+        generation: ((data >> 42) as u8) & GENERATION_MASK
+    }
+}
@@ -0,0 +1,257 @@
+#[cfg(test)]
+mod bitboard_unit_tests {
+    use crate::board_types::bitboard::{
+        BitBoard,
+        Constants,
+        generate_start_board
+    };
+    use crate::board_types::position::{
+        WHITE_KING_SIDE_CASTLE,
+        WHITE_QUEEN_SIDE_CASTLE,
+        BLACK_KING_SIDE_CASTLE,
+        BLACK_QUEEN_SIDE_CASTLE
+    };
+    use crate::enums::{
+        piece_color::PieceColor,
+        piece_num::PieceNum
+    };
+    use crate::functions::pos_to_num;
+    use crate::notation::move_from_uci;
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::tests::unit_tests::common::constants;
+
+    fn remove_piece(board: &mut BitBoard, piece: PieceNum, letter: u64, number: u64) {
+        board[piece as usize] &= !(1 << pos_to_num(letter, number));
+    }
+
+    fn move_strings(board: &BitBoard, turn: PieceColor, constants: &Constants) -> Vec<String> {
+        let mut board = *board;
+        board.turn = turn;
+        board.generate_moves(constants).unwrap().into_iter().map(|mov| mov.to_uci()).collect()
+    }
+
+    fn make_move(board: &BitBoard, turn: PieceColor, mov: &str, constants: &Constants) -> BitBoard {
+        let mut board = *board;
+        board.turn = turn;
+        board.after_move(move_from_uci(&board, mov, constants).unwrap()).unwrap()
+    }
+
+    // Start board with the pieces between the kings and rooks removed
+    fn open_back_ranks() -> BitBoard {
+        let mut board = generate_start_board();
+
+        for letter in [1, 6] {
+            remove_piece(&mut board, PieceNum::WhiteKnight, letter, 0);
+            remove_piece(&mut board, PieceNum::BlackKnight, letter, 7);
+        }
+        for letter in [2, 5] {
+            remove_piece(&mut board, PieceNum::WhiteBishop, letter, 0);
+            remove_piece(&mut board, PieceNum::BlackBishop, letter, 7);
+        }
+        remove_piece(&mut board, PieceNum::WhiteQueen, 3, 0);
+        remove_piece(&mut board, PieceNum::BlackQueen, 3, 7);
+
+        board
+    }
+
+    #[test]
+    fn castling_both_sides() {
+        let constants = constants();
+        let board = open_back_ranks();
+
+        let white_moves = move_strings(&board, PieceColor::White, constants);
+        assert!(white_moves.contains(&"e1g1".to_string()));
+        assert!(white_moves.contains(&"e1c1".to_string()));
+
+        let black_moves = move_strings(&board, PieceColor::Black, constants);
+        assert!(black_moves.contains(&"e8g8".to_string()));
+        assert!(black_moves.contains(&"e8c8".to_string()));
+
+        let castled = make_move(&board, PieceColor::White, "e1g1", constants);
+        assert_eq!(castled[PieceNum::WhiteKing as usize], 1 << pos_to_num(6, 0));
+        assert_eq!(castled[PieceNum::WhiteRook as usize], (1 << pos_to_num(0, 0)) | (1 << pos_to_num(5, 0)));
+        assert_eq!(castled.castling_rights, BLACK_KING_SIDE_CASTLE | BLACK_QUEEN_SIDE_CASTLE);
+    }
+
+    #[test]
+    fn no_castling_through_check() {
+        let constants = constants();
+        let mut board = open_back_ranks();
+
+        // Black rook on f3 attacks f1 through the missing pawn
+        remove_piece(&mut board, PieceNum::WhitePawn, 5, 1);
+        board[PieceNum::BlackRook as usize] |= 1 << pos_to_num(5, 2);
+
+        let white_moves = move_strings(&board, PieceColor::White, constants);
+        assert!(!white_moves.contains(&"e1g1".to_string()));
+        assert!(white_moves.contains(&"e1c1".to_string()));
+    }
+
+    #[test]
+    fn castling_rights_lost_after_rook_move() {
+        let constants = constants();
+        let board = open_back_ranks();
+
+        let moved = make_move(&board, PieceColor::White, "a1b1", constants);
+        assert_eq!(moved.castling_rights & WHITE_QUEEN_SIDE_CASTLE, 0);
+        assert_eq!(moved.castling_rights & WHITE_KING_SIDE_CASTLE, WHITE_KING_SIDE_CASTLE);
+
+        let white_moves = move_strings(&moved, PieceColor::White, constants);
+        assert!(!white_moves.iter().any(|mov_str| mov_str.starts_with("e1c1")));
+    }
+
+    #[test]
+    fn en_passant_capture() {
+        let constants = constants();
+        let mut board = generate_start_board();
+
+        // Black pawn from d7 to d4
+        remove_piece(&mut board, PieceNum::BlackPawn, 3, 6);
+        board[PieceNum::BlackPawn as usize] |= 1 << pos_to_num(3, 3);
+
+        let board = make_move(&board, PieceColor::White, "e2e4", constants);
+        assert_eq!(board.en_passant, 1 << pos_to_num(4, 2));
+
+        let captured = make_move(&board, PieceColor::Black, "d4e3", constants);
+        assert_eq!(captured[PieceNum::WhitePawn as usize] & (1 << pos_to_num(4, 3)), 0);
+        assert_eq!(captured[PieceNum::BlackPawn as usize] & (1 << pos_to_num(4, 2)), 1 << pos_to_num(4, 2));
+        assert_eq!(captured.en_passant, 0);
+    }
+
+    #[test]
+    fn en_passant_expires() {
+        let constants = constants();
+        let mut board = generate_start_board();
+
+        remove_piece(&mut board, PieceNum::BlackPawn, 3, 6);
+        board[PieceNum::BlackPawn as usize] |= 1 << pos_to_num(3, 3);
+
+        let board = make_move(&board, PieceColor::White, "e2e4", constants);
+        let board = make_move(&board, PieceColor::Black, "g8f6", constants);
+        let board = make_move(&board, PieceColor::White, "b1c3", constants);
+
+        let black_moves = move_strings(&board, PieceColor::Black, constants);
+        assert!(!black_moves.contains(&"d4e3".to_string()));
+    }
+
+    #[test]
+    fn underpromotion() {
+        let constants = constants();
+        let mut board = BitBoard::new_empty_board();
+        board[PieceNum::WhiteKing as usize] = 1 << pos_to_num(0, 0);
+        board[PieceNum::BlackKing as usize] = 1 << pos_to_num(0, 7);
+        board[PieceNum::WhitePawn as usize] = 1 << pos_to_num(4, 6);
+        board[PieceNum::BlackRook as usize] = 1 << pos_to_num(3, 7);
+
+        let white_moves = move_strings(&board, PieceColor::White, constants);
+        for mov in ["e7e8q", "e7e8r", "e7e8b", "e7e8n", "e7d8q", "e7d8r", "e7d8b", "e7d8n"] {
+            assert!(white_moves.contains(&mov.to_string()), "{} missing", mov);
+        }
+        assert!(!white_moves.contains(&"e7e8".to_string()));
+
+        let promoted = make_move(&board, PieceColor::White, "e7d8n", constants);
+        assert_eq!(promoted[PieceNum::WhiteKnight as usize], 1 << pos_to_num(3, 7));
+        assert_eq!(promoted[PieceNum::WhitePawn as usize], 0);
+        assert_eq!(promoted[PieceNum::BlackRook as usize], 0);
+    }
+
+    #[test]
+    fn position_state_after_moves() {
+        let constants = constants();
+        let board = generate_start_board();
+
+        let board = make_move(&board, PieceColor::White, "g1f3", constants);
+        assert_eq!(board.turn, PieceColor::Black);
+        assert_eq!(board.halfmove_clock, 1);
+        assert_eq!(board.fullmove_number, 1);
+
+        let board = make_move(&board, PieceColor::Black, "g8f6", constants);
+        assert_eq!(board.turn, PieceColor::White);
+        assert_eq!(board.halfmove_clock, 2);
+        assert_eq!(board.fullmove_number, 2);
+
+        let board = make_move(&board, PieceColor::White, "e2e4", constants);
+        assert_eq!(board.halfmove_clock, 0);
+        assert_eq!(board.en_passant, 1 << pos_to_num(4, 2));
+
+        let board = make_move(&board, PieceColor::Black, "f6e4", constants);
+        assert_eq!(board.halfmove_clock, 0);
+        assert_eq!(board.en_passant, 0);
+        assert_eq!(board.fullmove_number, 3);
+    }
+
+    #[test]
+    fn bishop_reaches_the_far_corner() {
+        let board = BitBoard::from_fen("7k/8/8/8/8/8/1K6/7b b - - 0 1").unwrap();
+        let moves = move_strings(&board, PieceColor::Black, constants());
+
+        assert!(moves.contains(&"h1a8".to_string()));
+        assert!(moves.contains(&"h1b7".to_string()));
+    }
+
+    // Castling, en passant and promotions (with and without a capture) are all possible from these
+    #[test]
+    fn unmake_move_restores_the_position() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbq1k1r/pp1Pbppp/2p5/8/1PB5/8/P1P1NnPP/RNBQK2R b KQ b3 0 8",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1"
+        ];
+
+        for fen in fens {
+            let mut board = BitBoard::from_fen(fen).unwrap();
+            let original = board;
+
+            for mov in board.generate_moves(constants()).unwrap() {
+                let undo = ChessBoardContract::make_move(&mut board, mov).unwrap();
+
+                for reply in board.generate_moves(constants()).unwrap() {
+                    let after_move = board;
+                    let reply_undo = ChessBoardContract::make_move(&mut board, reply).unwrap();
+                    board.unmake_move(reply, reply_undo);
+                    assert_eq!(board, after_move, "{} then {} from {}", mov.to_uci(), reply.to_uci(), fen);
+                }
+
+                board.unmake_move(mov, undo);
+                assert_eq!(board, original, "{} from {}", mov.to_uci(), fen);
+            }
+        }
+    }
+
+    #[test]
+    fn null_move_passes_the_turn() {
+        // The en passant square is lost, as the pawn that could take it doesn't move
+        let mut board = BitBoard::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+        let original = board;
+
+        let undo = board.make_null_move();
+        assert_eq!(board.to_fen(), "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR b KQkq - 1 3");
+        assert_eq!(board.get_hash(), BitBoard::from_fen(&board.to_fen()).unwrap().get_hash());
+
+        board.unmake_null_move(undo);
+        assert_eq!(board, original);
+    }
+
+    #[test]
+    fn captures_match_the_full_move_list() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            // Only captures that get the king out of check are legal
+            "4k3/8/8/8/1b6/8/3P4/4K2r w - - 0 1"
+        ];
+
+        for fen in fens {
+            let board = BitBoard::from_fen(fen).unwrap();
+            let captures: Vec<String> = board.generate_moves(constants()).unwrap()
+                                             .into_iter()
+                                             .filter(|mov| mov.is_capture() || mov.promotion().is_some())
+                                             .map(|mov| mov.to_uci())
+                                             .collect();
+
+            let generated: Vec<String> = board.generate_captures(constants()).unwrap().into_iter().map(|mov| mov.to_uci()).collect();
+            assert_eq!(generated, captures, "{}", fen);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+#![cfg(test)]
+
+use crate::board_types::bitboard::Constants;
+use std::sync::OnceLock;
+
+// Building the constants allocates the attack tables, so all tests share one instance
+pub fn constants() -> &'static Constants {
+    static CONSTANTS: OnceLock<Constants> = OnceLock::new();
+    CONSTANTS.get_or_init(Constants::new)
+}
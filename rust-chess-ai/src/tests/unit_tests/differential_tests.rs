@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod differential_unit_tests {
+    use crate::board_types::bitboard::{BitBoard, board_to_bitboard};
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::enums::end_type::EndType;
+    use crate::board_types::chess_move::Move;
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::notation::move_from_uci;
+    use crate::tests::unit_tests::common::constants;
+
+    const PIECE_VALUES: [i32; 6] = [100, 500, 300, 300, 900, 0];
+    const MAX_PLIES: usize = 300;
+
+    // Xorshift, so a failing game can be played again from its seed
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+    }
+
+    enum Replay {
+        Match,
+        Mismatch(String)
+    }
+
+    fn sorted_moves<T: ChessBoardContract>(board: &T) -> Vec<Move> {
+        let mut moves = board.generate_moves(constants()).unwrap();
+        moves.sort_by_key(|mov| mov.key());
+        moves
+    }
+
+    // Describes the first difference between the two boards, if any
+    fn compare(normal_board: &NormalBoard, bit_board: &BitBoard) -> Option<String> {
+        let converted = board_to_bitboard(normal_board);
+        if converted != *bit_board {
+            return Some(format!("positions differ: NormalBoard {} ({:x}), BitBoard {} ({:x})",
+                                converted.to_fen(), converted.hash, bit_board.to_fen(), bit_board.hash));
+        }
+
+        // The moves have to agree on the piece, capture and flags as well as the squares
+        let normal_moves = sorted_moves(normal_board);
+        let bit_moves = sorted_moves(bit_board);
+        if normal_moves != bit_moves {
+            let only_normal: Vec<&Move> = normal_moves.iter().filter(|mov| !bit_moves.contains(mov)).collect();
+            let only_bit: Vec<&Move> = bit_moves.iter().filter(|mov| !normal_moves.contains(mov)).collect();
+            return Some(format!("moves differ: only NormalBoard {:?}, only BitBoard {:?}", only_normal, only_bit));
+        }
+
+        let normal_end = normal_board.check_game_end(constants()).unwrap();
+        let bit_end = bit_board.check_game_end(constants()).unwrap();
+        if normal_end != bit_end {
+            return Some(format!("game end differs: NormalBoard {:?}, BitBoard {:?}", normal_end, bit_end));
+        }
+
+        let normal_value = normal_board.get_value_of_pieces(PIECE_VALUES);
+        let bit_value = bit_board.get_value_of_pieces(PIECE_VALUES);
+        if normal_value != bit_value {
+            return Some(format!("piece values differ: NormalBoard {}, BitBoard {}", normal_value, bit_value));
+        }
+
+        None
+    }
+
+    fn play<T: ChessBoardContract>(board: &T, mov: &str) -> Option<T> {
+        let mov = move_from_uci(board, mov, constants()).ok()?;
+        board.after_move(mov).ok()
+    }
+
+    // Plays the moves on both boards from the given position, comparing them after every move
+    fn replay(fen: &str, moves: &[String]) -> Replay {
+        let mut normal_board = NormalBoard::from_fen(fen).unwrap();
+        let mut bit_board = BitBoard::from_fen(fen).unwrap();
+
+        if let Some(mismatch) = compare(&normal_board, &bit_board) {
+            return Replay::Mismatch(mismatch);
+        }
+
+        for mov in moves {
+            match (play(&normal_board, mov), play(&bit_board, mov)) {
+                (Some(new_normal_board), Some(new_bit_board)) => {
+                    normal_board = new_normal_board;
+                    bit_board = new_bit_board;
+                },
+                _ => return Replay::Mismatch(format!("{} can only be played on one of the boards", mov))
+            }
+
+            if let Some(mismatch) = compare(&normal_board, &bit_board) {
+                return Replay::Mismatch(mismatch);
+            }
+        }
+
+        Replay::Match
+    }
+
+    // Plays random moves until the game ends. On a mismatch, returns the FEN of every position
+    // before it, the moves between them and the mismatch
+    fn random_game(rng: &mut Rng) -> Option<(Vec<String>, Vec<String>, String)> {
+        let mut normal_board = NormalBoard::new_board();
+        let mut bit_board = BitBoard::new_board();
+        let mut fens = Vec::new();
+        let mut moves = Vec::new();
+
+        for _ in 0..MAX_PLIES {
+            if let Some(mismatch) = compare(&normal_board, &bit_board) {
+                return Some((fens, moves, mismatch));
+            }
+            if normal_board.check_game_end(constants()).unwrap() != EndType::NoEnd {
+                return None;
+            }
+
+            let normal_moves = sorted_moves(&normal_board);
+            let mov = normal_moves[(rng.next() % normal_moves.len() as u64) as usize];
+
+            fens.push(bit_board.to_fen());
+            // The move lists are equal, so the BitBoard always has the move
+            bit_board = bit_board.after_move(mov).unwrap();
+            normal_board = normal_board.after_move(mov).unwrap();
+            moves.push(mov.to_uci());
+        }
+
+        None
+    }
+
+    // The shortest end of the game that still leads to the mismatch when played from the FEN
+    // of the position before it. Often the FEN of the mismatching position is enough. If no FEN
+    // reproduces it, it depends on NormalBoard state a FEN doesn't carry, like which pieces have
+    // moved, and only the whole game from the start position does
+    fn minimal_repro(fens: &[String], moves: &[String], mismatch: String) -> (String, Vec<String>, String) {
+        let mismatch_fen = match fens.last() {
+            Some(_) => {
+                let last = moves.len() - 1;
+                let board = BitBoard::from_fen(&fens[last]).unwrap();
+                play(&board, &moves[last]).unwrap().to_fen()
+            },
+            None => BitBoard::new_board().to_fen()
+        };
+
+        if let Replay::Mismatch(mismatch) = replay(&mismatch_fen, &[]) {
+            return (mismatch_fen, Vec::new(), mismatch);
+        }
+
+        for start in (0..moves.len()).rev() {
+            if let Replay::Mismatch(mismatch) = replay(&fens[start], &moves[start..]) {
+                return (fens[start].clone(), moves[start..].to_vec(), mismatch);
+            }
+        }
+
+        (BitBoard::new_board().to_fen(), moves.to_vec(), format!("{} (not reproducible from a FEN)", mismatch))
+    }
+
+    fn run_random_games(games: u64, seed: u64) {
+        for game in 0..games {
+            let game_seed = seed + game;
+            let mut rng = Rng(game_seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1);
+
+            if let Some((fens, moves, mismatch)) = random_game(&mut rng) {
+                let (fen, moves, mismatch) = minimal_repro(&fens, &moves, mismatch);
+                panic!("Game with seed {} ends in a mismatch after playing {:?} from {}: {}", game_seed, moves, fen, mismatch);
+            }
+        }
+    }
+
+    #[test]
+    fn random_games_agree() {
+        run_random_games(10, 0);
+    }
+
+    // Too slow for every test run, use cargo test --release -- --ignored
+    #[test]
+    #[ignore]
+    fn many_random_games_agree() {
+        run_random_games(2000, 1000);
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod draw_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::board_types::position::START_FEN;
+    use crate::enums::{
+        end_type::EndType,
+        tie_type::TieType,
+        piece_color::PieceColor
+    };
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::notation::move_from_uci;
+    use crate::tests::unit_tests::common::constants;
+
+    fn game_end<T: ChessBoardContract>(fen: &str) -> EndType {
+        T::from_fen(fen).unwrap().check_game_end(constants()).unwrap()
+    }
+
+    fn check_game_ends<T: ChessBoardContract>() {
+        // Fool's mate
+        assert_eq!(game_end::<T>("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"), EndType::Checkmate(PieceColor::Black));
+        assert_eq!(game_end::<T>("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), EndType::Tie(TieType::Stalemate));
+
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/4P3/4K3 w - - 100 80"), EndType::Tie(TieType::FiftyMoveRule));
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/4P3/4K3 w - - 99 80"), EndType::NoEnd);
+        // Checkmate on the last move before the fifty-move rule still counts
+        assert_eq!(game_end::<T>("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 100 53"), EndType::Checkmate(PieceColor::Black));
+    }
+
+    fn check_insufficient_material<T: ChessBoardContract>() {
+        let insufficient = EndType::Tie(TieType::InsufficientMaterial);
+
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/8/4K3 w - - 0 1"), insufficient);
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"), insufficient);
+        assert_eq!(game_end::<T>("4kb2/8/8/8/8/8/8/4K3 w - - 0 1"), insufficient);
+        // Bishops on squares of the same color
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/8/1B2KB2 w - - 0 1"), insufficient);
+        assert_eq!(game_end::<T>("2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1"), insufficient);
+
+        assert_eq!(game_end::<T>("5b2/4k3/8/8/8/8/8/4KB2 w - - 0 1"), EndType::NoEnd);
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/8/4KNN1 w - - 0 1"), EndType::NoEnd);
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"), EndType::NoEnd);
+        assert_eq!(game_end::<T>("4k3/8/8/8/8/8/8/R3K3 w - - 0 1"), EndType::NoEnd);
+    }
+
+    fn check_threefold_repetition<T: ChessBoardContract>() {
+        let mut history = vec![T::from_fen(START_FEN).unwrap()];
+        let knight_moves = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+        // The start position comes back after every four moves
+        for (i, mov) in knight_moves.iter().cycle().take(8).enumerate() {
+            let current_board = &history[history.len() - 1];
+            let board = current_board.after_move(move_from_uci(current_board, mov, constants()).unwrap()).unwrap();
+
+            let expected = match i {
+                7 => EndType::Tie(TieType::ThreefoldRepetition),
+                _ => EndType::NoEnd
+            };
+            assert_eq!(board.check_repetition(&history), expected, "after {} moves", i + 1);
+
+            history.push(board);
+        }
+    }
+
+    #[test]
+    fn bitboard_game_ends() {
+        check_game_ends::<BitBoard>();
+        check_insufficient_material::<BitBoard>();
+        check_threefold_repetition::<BitBoard>();
+    }
+
+    #[test]
+    fn normalboard_game_ends() {
+        check_game_ends::<NormalBoard>();
+        check_insufficient_material::<NormalBoard>();
+        check_threefold_repetition::<NormalBoard>();
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod evaluation_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::enums::evaluation_type::{EvaluationType, EVALUATION_TYPES};
+    use crate::enums::piece_color::PieceColor;
+    use crate::evaluation_functions::piece_square_evaluation::piece_square_evaluation;
+    use crate::evaluation_functions::board_piece_evaluation::board_piece_evaluation;
+    use crate::evaluation_functions::score::{mate_score, mate_in_moves, is_mate_score, score_to_tt, score_from_tt, MATE_SCORE};
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::tests::unit_tests::common::constants;
+
+    const FENS: [&str; 4] = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkb1r/pp1p1ppp/4pn2/2p5/3P4/2P2N2/PP2PPPP/RNBQKB1R w KQkq - 0 4",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "4k3/8/8/8/3QK3/8/8/8 b - - 0 1"
+    ];
+
+    fn evaluate<T: ChessBoardContract>(fen: &str) -> i32 {
+        piece_square_evaluation(&T::from_fen(fen).unwrap(), 0, constants()).unwrap()
+    }
+
+    // The same position with the colors swapped and the board turned around
+    fn mirror_fen(fen: &str) -> String {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        let swap_case = |s: &str| -> String {
+            s.chars().map(|c| match c.is_ascii_uppercase() {
+                true => c.to_ascii_lowercase(),
+                false => c.to_ascii_uppercase()
+            }).collect()
+        };
+
+        let board = fields[0].split('/').rev().map(swap_case).collect::<Vec<String>>().join("/");
+        let turn = match fields[1] {
+            "w" => "b",
+            _ => "w"
+        };
+        let mut castling: Vec<char> = swap_case(fields[2]).chars().collect();
+        castling.sort_by_key(|c| c.is_ascii_lowercase());
+
+        format!("{} {} {} - {} {}", board, turn, castling.iter().collect::<String>(), fields[4], fields[5])
+    }
+
+    #[test]
+    fn evaluation_is_symmetric() {
+        assert_eq!(evaluate::<BitBoard>("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), 0);
+
+        for fen in FENS.iter() {
+            let score = evaluate::<BitBoard>(fen);
+            assert_eq!(evaluate::<BitBoard>(&mirror_fen(fen)), -score, "{}", fen);
+            assert_eq!(evaluate::<NormalBoard>(fen), score, "{}", fen);
+        }
+    }
+
+    #[test]
+    fn king_placement_depends_on_the_phase() {
+        // With queens and rooks left the king is safer in the corner, without them in the center
+        let middlegame_corner = evaluate::<BitBoard>("r2qk2r/pppppppp/8/8/8/8/PPPPPPPP/R2Q1RK1 w kq - 0 1");
+        let middlegame_center = evaluate::<BitBoard>("r2qk2r/pppppppp/8/8/4K3/8/PPPPPPPP/R2Q1R2 w kq - 0 1");
+        assert!(middlegame_corner > middlegame_center);
+
+        let endgame_corner = evaluate::<BitBoard>("4k3/pppppppp/8/8/8/8/PPPPPPPP/6K1 w - - 0 1");
+        let endgame_center = evaluate::<BitBoard>("4k3/pppppppp/8/8/4K3/8/PPPPPPPP/8 w - - 0 1");
+        assert!(endgame_corner < endgame_center);
+    }
+
+    #[test]
+    fn mate_scores() {
+        // Fool's mate, and the same mate with the colors swapped
+        let black_wins = BitBoard::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+        let white_wins = BitBoard::from_fen(&mirror_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")).unwrap();
+
+        assert_eq!(board_piece_evaluation(&black_wins, 4, constants()), Ok(-MATE_SCORE + 4));
+        assert_eq!(piece_square_evaluation(&white_wins, 4, constants()), Ok(MATE_SCORE - 4));
+        assert_eq!(board_piece_evaluation(&white_wins, 2, constants()), Ok(mate_score(PieceColor::White, 2)));
+
+        // Mate on the first ply is mate in one move, on the second ply the other side mates in one
+        assert_eq!(mate_in_moves(mate_score(PieceColor::White, 1)), Some(1));
+        assert_eq!(mate_in_moves(mate_score(PieceColor::Black, 2)), Some(-1));
+        assert_eq!(mate_in_moves(mate_score(PieceColor::White, 5)), Some(3));
+        assert_eq!(mate_in_moves(2500), None);
+        assert!(!is_mate_score(evaluate::<BitBoard>(FENS[0])));
+
+        // A mate 5 plies from the root found at ply 3 is stored as a mate in 2 plies
+        let score = mate_score(PieceColor::Black, 5);
+        assert_eq!(score_to_tt(score, 3), mate_score(PieceColor::Black, 2));
+        assert_eq!(score_from_tt(score_to_tt(score, 3), 3), score);
+        assert_eq!(score_from_tt(score_to_tt(150, 3), 7), 150);
+    }
+
+    #[test]
+    fn evaluation_types_by_name() {
+        for typ in EVALUATION_TYPES.iter() {
+            assert_eq!(EvaluationType::from_string(&typ.get_string()), Some(*typ));
+        }
+        assert_eq!(EvaluationType::from_string("piecesquare"), Some(EvaluationType::PieceSquare));
+        assert_eq!(EvaluationType::from_string("Neural"), None);
+
+        let board = BitBoard::from_fen(FENS[1]).unwrap();
+        assert_eq!(EvaluationType::PieceSquare.get_function()(&board, 0, constants()), piece_square_evaluation(&board, 0, constants()));
+    }
+}
@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod fen_unit_tests {
+    use crate::board_types::bitboard::{BitBoard, generate_start_board};
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::board_types::position::START_FEN;
+    use crate::enums::{
+        chess_error::ChessError,
+        piece_color::PieceColor,
+        piece_type::PieceType
+    };
+    use crate::traits::chess_board_contract::ChessBoardContract;
+
+    const FENS: [&str; 4] = [
+        START_FEN,
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 b - - 13 37"
+    ];
+
+    #[test]
+    fn start_position() {
+        assert_eq!(BitBoard::from_fen(START_FEN).unwrap(), generate_start_board());
+        assert_eq!(generate_start_board().to_fen(), START_FEN);
+        assert_eq!(NormalBoard::new_start_board().to_fen(), START_FEN);
+    }
+
+    #[test]
+    fn round_trip() {
+        for fen in FENS {
+            assert_eq!(BitBoard::from_fen(fen).unwrap().to_fen(), fen);
+            assert_eq!(NormalBoard::from_fen(fen).unwrap().to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn optional_clocks() {
+        let board = BitBoard::from_fen("4k3/8/8/8/8/8/8/4K3 b - -").unwrap();
+        assert_eq!(board.turn, PieceColor::Black);
+        assert_eq!(board.halfmove_clock, 0);
+        assert_eq!(board.fullmove_number, 1);
+    }
+
+    #[test]
+    fn normalboard_pieces() {
+        let board = NormalBoard::from_fen(FENS[1]).unwrap();
+
+        let king = board.get_piece(4, 0).unwrap().unwrap();
+        assert_eq!(king.typ, PieceType::King);
+        assert_eq!(king.color, PieceColor::White);
+        assert!(!king.has_moved());
+
+        let pawn = board.get_piece(3, 4).unwrap().unwrap();
+        assert_eq!(pawn.typ, PieceType::Pawn);
+        assert!(pawn.has_moved());
+    }
+
+    #[test]
+    fn malformed_fen() {
+        let cases = [
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq", ChessError::InvalidFenFieldCount),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1", ChessError::InvalidFenBoard),
+            ("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", ChessError::InvalidFenBoard),
+            ("rnbqkbnr/ppppxppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", ChessError::InvalidFenBoard),
+            ("rnbqqbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", ChessError::InvalidFenBoard),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1", ChessError::InvalidFenTurn),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkk - 0 1", ChessError::InvalidFenCastling),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e4 0 1", ChessError::InvalidFenEnPassant),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1", ChessError::InvalidFenClock),
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0", ChessError::InvalidFenClock)
+        ];
+
+        for (fen, err) in cases {
+            assert_eq!(BitBoard::from_fen(fen).err(), Some(err), "{}", fen);
+            assert_eq!(NormalBoard::from_fen(fen).err(), Some(err), "{}", fen);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+#[cfg(test)]
+mod functions_unit_tests {
+    use crate::functions::normalize_move_string;
+
+    #[test]
+    fn normalize_move_strings() {
+        assert_eq!(normalize_move_string("e2 e4"), Some("e2 e4".to_string()));
+        assert_eq!(normalize_move_string("e2e4\n"), Some("e2 e4".to_string()));
+        assert_eq!(normalize_move_string("e7e8N"), Some("e7 e8n".to_string()));
+        assert_eq!(normalize_move_string("e7 e8q"), Some("e7 e8q".to_string()));
+        assert_eq!(normalize_move_string("e7e8k"), None);
+        assert_eq!(normalize_move_string("i2 i4"), None);
+        assert_eq!(normalize_move_string("e2"), None);
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod iterative_deepening_unit_tests {
+    use crate::board_types::bitboard::{BitBoard, Constants};
+    use crate::enums::piece_color::PieceColor;
+    use crate::evaluation_functions::board_piece_evaluation::board_piece_evaluation;
+    use crate::evaluation_functions::score::{mate_score, mate_in_moves};
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::search::iterative_deepening::{iterative_deepening, time_budget, SearchLimits, SearchParams};
+    use crate::search::transposition_table::TranspositionTable;
+    use crate::search::search_options::SearchOptions;
+    use crate::tests::unit_tests::common::constants;
+    use crate::search::search_threads::SearchThreads;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+    use crate::turn_functions::minimax_move::SearchResult;
+    use std::time::{Duration, Instant};
+
+    // Searches with a new transposition table, panicking on errors
+    fn deepen<F>(board: &BitBoard, limits: SearchLimits, options: &SearchOptions, helpers: Option<&SearchThreads>, on_iteration: F) -> Option<SearchResult>
+        where F: FnMut(&SearchResult, Duration) {
+
+        let params = SearchParams {
+            board,
+            history: &[],
+            limits,
+            eval_func: board_piece_evaluation,
+            constants: constants(),
+            options,
+            tt: &Arc::new(TranspositionTable::new(1)),
+            stop: &AtomicBool::new(false)
+        };
+        iterative_deepening(&params, helpers, on_iteration).unwrap()
+    }
+
+    #[test]
+    fn time_budget_from_clock() {
+        let secs = Duration::from_secs;
+
+        assert_eq!(time_budget(secs(300), secs(0), None), secs(10));
+        assert_eq!(time_budget(secs(300), secs(4), None), secs(12));
+        assert_eq!(time_budget(secs(60), secs(0), Some(10)), secs(6));
+        // Never more than half of the remaining time
+        assert_eq!(time_budget(secs(10), secs(30), Some(1)), secs(5));
+    }
+
+    #[test]
+    fn searches_every_depth_up_to_the_limit() {
+        let board = BitBoard::from_fen("4k3/8/3r4/8/3P4/2N5/8/4K3 w - - 0 1").unwrap();
+        let limits = SearchLimits { depth: 3, time: None };
+        let mut depths = Vec::new();
+
+        let res = deepen(&board, limits, &SearchOptions::default(), None, |res, _| depths.push(res.depth)).unwrap();
+
+        assert_eq!(depths, vec![1, 2, 3]);
+        assert_eq!(res.depth, 3);
+    }
+
+    #[test]
+    fn finds_mates_for_both_colors() {
+        // Both need a rook sacrifice on the back rank first, so the mate is in two moves
+        for (fen, winner) in [("r5k1/5ppp/8/8/8/8/4RPPP/4R1K1 w - - 0 1", PieceColor::White),
+                              ("4r1k1/4rppp/8/8/8/8/5PPP/R5K1 b - - 0 1", PieceColor::Black)] {
+            let board = BitBoard::from_fen(fen).unwrap();
+            let limits = SearchLimits { depth: 6, time: None };
+
+            let res = deepen(&board, limits, &SearchOptions::default(), None, |_, _| ()).unwrap();
+
+            assert_eq!(res.score, mate_score(winner, 3), "{}", fen);
+            assert_eq!(mate_in_moves(res.score), Some(2 * winner.side_const()));
+            // Searching deeper can't find a shorter mate
+            assert_eq!(res.depth, 3);
+
+            let board = board.after_move(res.best_move).unwrap();
+            assert!(board.in_check(constants()));
+        }
+    }
+
+    #[test]
+    fn stops_when_the_time_runs_out() {
+        let board = BitBoard::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let limits = SearchLimits { depth: 20, time: Some(Duration::from_millis(200)) };
+        let start_time = Instant::now();
+
+        let res = deepen(&board, limits, &SearchOptions::default(), None, |_, _| ());
+
+        assert!(start_time.elapsed() < Duration::from_secs(5));
+        // The result comes from a finished iteration, not the one that got stopped
+        if let Some(res) = res {
+            assert!(res.depth < 20);
+        }
+    }
+
+    #[test]
+    fn helper_threads_are_kept_between_searches() {
+        let threads = SearchThreads::new(3, Arc::new(Constants::new()));
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        for _ in 0..2 {
+            let sender = sender.clone();
+            threads.run(move |i, _| sender.send((i, std::thread::current().id())).unwrap());
+        }
+        drop(sender);
+
+        let mut ids: Vec<_> = receiver.iter().collect();
+        ids.sort_by_key(|(i, _)| *i);
+        // Every thread got the job both times
+        assert_eq!(ids.iter().map(|(i, _)| *i).collect::<Vec<usize>>(), vec![0, 0, 1, 1, 2, 2]);
+        assert!(ids.chunks(2).all(|pair| pair[0].1 == pair[1].1));
+    }
+
+    #[test]
+    fn helper_threads_share_the_search() {
+        let board = BitBoard::from_fen("r5k1/5ppp/8/8/8/8/4RPPP/4R1K1 w - - 0 1").unwrap();
+        let limits = SearchLimits { depth: 6, time: None };
+        let options = SearchOptions { threads: 4, ..SearchOptions::default() };
+        let threads = SearchThreads::new(3, Arc::new(Constants::new()));
+
+        // The same helpers can be used again for the next search
+        for _ in 0..2 {
+            let res = deepen(&board, limits, &options, Some(&threads), |_, _| ()).unwrap();
+
+            assert_eq!(res.score, mate_score(PieceColor::White, 3));
+            assert!(board.after_move(res.best_move).unwrap().in_check(constants()));
+        }
+    }
+}
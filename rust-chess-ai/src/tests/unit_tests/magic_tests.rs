@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod magic_unit_tests {
+    use crate::board_types::magic::{
+        MagicTable,
+        slider_reach,
+        ROOK_DIRECTIONS,
+        BISHOP_DIRECTIONS
+    };
+    use crate::functions::pos_to_num;
+
+    // Random occupied boards with about one in four squares taken
+    fn random_boards(count: usize) -> Vec<u64> {
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        (0..count).map(|_| next() & next()).collect()
+    }
+
+    #[test]
+    fn magic_lookups_match_ray_walks() {
+        let rook = MagicTable::rook();
+        let bishop = MagicTable::bishop();
+
+        for occupied_board in random_boards(500).into_iter().chain([0, u64::MAX]) {
+            for i in 0..64 {
+                assert_eq!(rook.get(i, occupied_board), slider_reach(i, occupied_board, &ROOK_DIRECTIONS));
+                assert_eq!(bishop.get(i, occupied_board), slider_reach(i, occupied_board, &BISHOP_DIRECTIONS));
+            }
+        }
+    }
+
+    #[test]
+    fn reach_includes_first_blocker() {
+        let rook = MagicTable::rook();
+        let a1 = pos_to_num(0, 0) as usize;
+        let blocker = 1 << pos_to_num(0, 3);
+
+        // a2, a3, a4 and the whole first row
+        let expected = (1 << pos_to_num(0, 1)) | (1 << pos_to_num(0, 2)) | blocker | 0xFE;
+        assert_eq!(rook.get(a1, blocker), expected);
+
+        // Pieces behind the blocker and on the square itself change nothing
+        assert_eq!(rook.get(a1, blocker | (1 << pos_to_num(0, 5)) | 1), expected);
+    }
+}
@@ -1,12 +1,54 @@
 
 #[cfg(test)]
 mod normalboard_unit_tests {
-    use crate::board_types::normalboard::NormalBoard;
+    use crate::board_types::normalboard::{NormalBoard, ChessPiece};
     use crate::enums::{
         piece_color::PieceColor,
         piece_type::PieceType
     };
 
+    #[test]
+    fn underpromotion() {
+        let mut board = NormalBoard::new_empty_board();
+        board.set_piece(0, 0, Some(&ChessPiece::new(PieceType::King, PieceColor::White))).unwrap();
+        board.set_piece(0, 7, Some(&ChessPiece::new(PieceType::King, PieceColor::Black))).unwrap();
+        board.set_piece(4, 6, Some(&ChessPiece::new(PieceType::Pawn, PieceColor::White))).unwrap();
+
+        let moves = board.generate_possible_moves().unwrap();
+        let promotions = moves.iter().filter(|(mov_str, _)| mov_str.starts_with("e7 e8")).map(|(mov_str, _)| mov_str.as_str()).collect::<Vec<_>>();
+        assert_eq!(promotions, vec!["e7 e8q", "e7 e8r", "e7 e8b", "e7 e8n"]);
+
+        let (_, promoted) = moves.iter().find(|(mov_str, _)| mov_str == "e7 e8n").unwrap();
+        let piece = promoted.get_piece(4, 7).unwrap().unwrap();
+        assert_eq!(piece.typ, PieceType::Knight);
+        assert_eq!(piece.color, PieceColor::White);
+    }
+
+    #[test]
+    fn en_passant_uses_position_state() {
+        let board = NormalBoard::new_start_board();
+
+        let play = |board: &NormalBoard, mov: &str| -> NormalBoard {
+            let moves = board.generate_possible_moves().unwrap();
+            moves.into_iter().find(|(mov_str, _)| mov_str == mov).unwrap().1
+        };
+
+        let board = play(&board, "e2 e4");
+        let board = play(&board, "a7 a6");
+        let board = play(&board, "e4 e5");
+        let board = play(&board, "d7 d5");
+        assert_eq!(board.get_en_passant(), Some((3, 5)));
+
+        let captured = play(&board, "e5 d6");
+        assert!(captured.get_piece(3, 4).unwrap().is_none());
+        assert_eq!(captured.get_en_passant(), None);
+
+        // Only available directly after the double step
+        let board = play(&board, "b1 c3");
+        let board = play(&board, "a6 a5");
+        assert!(!board.generate_possible_moves().unwrap().iter().any(|(mov_str, _)| mov_str == "e5 d6"));
+    }
+
     // #[test]
     // fn make_empty_board() {
     //     let board = NormalBoard::new_empty_board();
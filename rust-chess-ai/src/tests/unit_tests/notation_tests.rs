@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod notation_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::enums::{
+        chess_error::ChessError,
+        piece_type::PieceType
+    };
+    use crate::notation::{move_from_uci, move_from_long_algebraic, move_to_san, move_from_san, parse_move};
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::tests::unit_tests::common::constants;
+
+    const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+    fn san(fen: &str, uci: &str) -> String {
+        let board = BitBoard::from_fen(fen).unwrap();
+        move_to_san(&board, move_from_uci(&board, uci, constants()).unwrap(), constants()).unwrap()
+    }
+
+    fn check_round_trips<T: ChessBoardContract>(fen: &str) {
+        let board = T::from_fen(fen).unwrap();
+
+        for mov in board.generate_moves(constants()).unwrap() {
+            assert_eq!(move_from_uci(&board, &mov.to_uci(), constants()), Ok(mov));
+            assert_eq!(move_from_long_algebraic(&board, &mov.to_long_algebraic(), constants()), Ok(mov));
+
+            let san = move_to_san(&board, mov, constants()).unwrap();
+            assert_eq!(move_from_san(&board, &san, constants()), Ok(mov), "{}", san);
+        }
+    }
+
+    #[test]
+    fn moves_from_uci() {
+        let board = BitBoard::new_board();
+
+        let mov = move_from_uci(&board, "e2e4", constants()).unwrap();
+        assert_eq!((mov.piece(), mov.is_double_pawn_push(), mov.to_uci()), (PieceType::Pawn, true, "e2e4".to_string()));
+        assert_eq!(move_from_uci(&board, "e2 e4", constants()), Ok(mov));
+        assert_eq!(move_from_uci(&board, "e2e5", constants()), Err(ChessError::InvalidMove));
+        assert_eq!(move_from_uci(&board, "e2", constants()), Err(ChessError::InvalidMoveString));
+
+        // UCI always has the piece of a promotion
+        let board = BitBoard::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(move_from_uci(&board, "a7a8", constants()), Err(ChessError::InvalidMove));
+        assert_eq!(move_from_uci(&board, "a7a8n", constants()).unwrap().promotion(), Some(PieceType::Knight));
+    }
+
+    #[test]
+    fn long_algebraic_notation() {
+        let board = BitBoard::from_fen(KIWIPETE).unwrap();
+        let notation = |uci: &str| move_from_uci(&board, uci, constants()).unwrap().to_long_algebraic();
+
+        assert_eq!(notation("e5f7"), "Ne5xf7");
+        assert_eq!(notation("a2a4"), "a2-a4");
+        assert_eq!(notation("e1c1"), "O-O-O");
+        assert_eq!(move_from_long_algebraic(&board, "Ne5-f7", constants()).unwrap().to_uci(), "e5f7");
+        assert_eq!(move_from_long_algebraic(&board, "Be5-f7", constants()), Err(ChessError::InvalidMove));
+    }
+
+    #[test]
+    fn san_of_moves() {
+        assert_eq!(san("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", "g1f3"), "Nf3");
+        assert_eq!(san("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2", "e4d5"), "exd5");
+        assert_eq!(san("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", "e5f6"), "exf6");
+        assert_eq!(san(KIWIPETE, "e1g1"), "O-O");
+        assert_eq!(san(KIWIPETE, "e1c1"), "O-O-O");
+        assert_eq!(san("7k/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8q"), "e8=Q+");
+        assert_eq!(san("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq g3 0 2", "d8h4"), "Qh4#");
+
+        // Disambiguation by file, by rank, and by both
+        assert_eq!(san("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", "b1d2"), "Nbd2");
+        assert_eq!(san("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3"), "R1a3");
+        assert_eq!(san("4k3/8/8/8/8/Q7/8/Q1Q4K w - - 0 1", "a1b2"), "Qa1b2");
+    }
+
+    #[test]
+    fn moves_from_san() {
+        let board = BitBoard::from_fen("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1").unwrap();
+        assert_eq!(move_from_san(&board, "Nd2", constants()), Err(ChessError::AmbiguousMove));
+        assert_eq!(move_from_san(&board, "Nfd2", constants()).unwrap().to_uci(), "f1d2");
+        assert_eq!(move_from_san(&board, "Nb1d2", constants()).unwrap().to_uci(), "b1d2");
+        assert_eq!(move_from_san(&board, "Ne4", constants()), Err(ChessError::InvalidMove));
+
+        let board = BitBoard::from_fen(KIWIPETE).unwrap();
+        assert_eq!(move_from_san(&board, "0-0", constants()).unwrap().to_uci(), "e1g1");
+        assert_eq!(move_from_san(&board, "Nxf7!?", constants()).unwrap().to_uci(), "e5f7");
+        assert_eq!(move_from_san(&board, "dxe6", constants()).unwrap().to_uci(), "d5e6");
+
+        let board = BitBoard::from_fen("7k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(move_from_san(&board, "e8=N", constants()).unwrap().promotion(), Some(PieceType::Knight));
+        assert_eq!(move_from_san(&board, "e8Q+", constants()).unwrap().promotion(), Some(PieceType::Queen));
+    }
+
+    #[test]
+    fn parse_typed_moves() {
+        let board = BitBoard::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+        let parse = |move_str: &str| parse_move(&board, move_str, constants()).map(|mov| mov.to_uci());
+
+        assert_eq!(parse("g1f3\n"), Ok("g1f3".to_string()));
+        assert_eq!(parse("g1 f3"), Ok("g1f3".to_string()));
+        assert_eq!(parse("Nf3"), Ok("g1f3".to_string()));
+        assert_eq!(parse("Ng1-f3"), Ok("g1f3".to_string()));
+        assert_eq!(parse("exf6 e.p."), Ok("e5f6".to_string()));
+        assert_eq!(parse("e5xf6"), Ok("e5f6".to_string()));
+        assert_eq!(parse("Nf4"), Err(ChessError::InvalidMove));
+        assert_eq!(parse("hello"), Err(ChessError::InvalidMoveString));
+
+        let board = BitBoard::from_fen("4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1").unwrap();
+        assert_eq!(parse_move(&board, "Nd2", constants()), Err(ChessError::AmbiguousMove));
+
+        // Typed promotions without the piece are promotions to a queen
+        let board = BitBoard::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        for move_str in ["a7a8", "a7 a8\n", "a8+", "a7-a8"] {
+            assert_eq!(parse_move(&board, move_str, constants()).unwrap().promotion(), Some(PieceType::Queen), "{}", move_str);
+        }
+        assert_eq!(parse_move(&board, "a8=N", constants()).unwrap().promotion(), Some(PieceType::Knight));
+    }
+
+    #[test]
+    fn notation_round_trips() {
+        for fen in [KIWIPETE, "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1", "4k3/8/8/8/8/Q7/8/Q1Q4K w - - 0 1"] {
+            check_round_trips::<BitBoard>(fen);
+        }
+        check_round_trips::<NormalBoard>(KIWIPETE);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod perft_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::board_types::position::START_FEN;
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::tests::unit_tests::common::constants;
+
+    // Known node counts for depth 1, 2, 3... from the chess programming wiki
+    const PERFT_POSITIONS: [(&str, &[u64]); 6] = [
+        (START_FEN, &[20, 400, 8902, 197281]),
+        // Kiwipete
+        ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", &[48, 2039, 97862]),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2812, 43238]),
+        ("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", &[6, 264, 9467]),
+        ("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", &[44, 1486, 62379]),
+        ("r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10", &[46, 2079, 89890])
+    ];
+
+    // Node counts above max_nodes are skipped to keep the tests fast
+    fn check_perft<T: ChessBoardContract>(max_nodes: u64) {
+        for (fen, counts) in PERFT_POSITIONS {
+            let board = T::from_fen(fen).unwrap();
+
+            for (i, count) in counts.iter().enumerate().filter(|(_, count)| **count <= max_nodes) {
+                let depth = i as u32 + 1;
+                assert_eq!(board.perft(depth, constants()).unwrap(), *count, "perft({}) of {}", depth, fen);
+            }
+        }
+    }
+
+    #[test]
+    fn bitboard_perft() {
+        check_perft::<BitBoard>(100_000);
+    }
+
+    #[test]
+    fn normalboard_perft() {
+        check_perft::<NormalBoard>(10_000);
+    }
+
+    #[test]
+    fn divide_adds_up_to_perft() {
+        let board = BitBoard::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1").unwrap();
+        let divide = board.divide(2, constants()).unwrap();
+
+        assert_eq!(divide.len(), 48);
+        assert_eq!(divide.iter().map(|(_, nodes)| nodes).sum::<u64>(), 2039);
+        assert!(divide.iter().any(|(mov, nodes)| mov.to_uci() == "e1g1" && *nodes == 43));
+    }
+}
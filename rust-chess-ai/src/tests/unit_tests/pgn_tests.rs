@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod pgn_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::enums::{
+        end_type::EndType,
+        piece_color::PieceColor,
+        chess_error::ChessError
+    };
+    use crate::game::Game;
+    use crate::notation::move_from_san;
+    use crate::pgn::{PgnGame, PgnMove, PgnError, parse_pgn, date_today, clock_string};
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::tests::unit_tests::common::constants;
+
+    use std::time::Duration;
+
+    const GAMES: &str = concat!(
+        "[Event \"Reference \\\"games\\\"\"]\n",
+        "[White \"Anderssen\"]\n",
+        "[Result \"1-0\"]\n",
+        "\n",
+        "{Opening comment} 1. e4 e5 2. Nf3 (2. f4 exf4 (2... d5) 3. Nf3) 2... Nc6! $14 {Developing; the knight} 3.Bb5 a6?!\n",
+        "; rest of the line\n",
+        "% escaped line 4. Qh5\n",
+        "4. Ba4 1-0\n",
+        "\n",
+        "[Event \"No result [A]\"]\n",
+        "1. d4 d5\n",
+        "[Event \"Illegal\"]\n",
+        "[SetUp \"1\"]\n",
+        "[FEN \"4k3/8/8/8/8/8/8/4K2R w K - 0 1\"]\n",
+        "1. O-O Kd7 2. Rf2 Kc6 3. Rf1 Kb5 4. O-O *\n"
+    );
+
+    // Plays the move in the game and on the board that follows it
+    fn play(game: &mut Game<BitBoard>, board: &mut BitBoard, san: &str, comment: Option<&str>) -> EndType {
+        let mov = move_from_san(board, san, constants()).unwrap();
+        *board = board.after_move(mov).unwrap();
+        game.play_move(mov, comment.map(|comment| comment.to_string())).unwrap()
+    }
+
+    fn fools_mate() -> Game<BitBoard> {
+        let mut game = Game::new();
+        let mut board = BitBoard::new_board();
+
+        for (san, comment) in [("f3", None), ("e5", Some("[%eval -0.30]")), ("g4", None)] {
+            assert_eq!(play(&mut game, &mut board, san, comment), EndType::NoEnd);
+        }
+        assert_eq!(play(&mut game, &mut board, "Qh4", None), EndType::Checkmate(PieceColor::Black));
+
+        game
+    }
+
+    #[test]
+    fn export_finished_game() {
+        let mut game = fools_mate();
+        game.set_tag("Event", "Test \"game\"");
+
+        assert_eq!(game.get_record().to_pgn(false), concat!(
+            "[Event \"Test \\\"game\\\"\"]\n",
+            "[Site \"?\"]\n",
+            "[Date \"????.??.??\"]\n",
+            "[Round \"?\"]\n",
+            "[White \"?\"]\n",
+            "[Black \"?\"]\n",
+            "[Result \"0-1\"]\n",
+            "\n",
+            "1. f3 e5 2. g4 Qh4# 0-1\n"
+        ));
+
+        // Black's move after a comment gets its number again
+        assert!(game.get_record().to_pgn(true).ends_with("\n1. f3 e5 {[%eval -0.30]} 2. g4 Qh4# 0-1\n"));
+        assert_eq!(EndType::Checkmate(PieceColor::Black).get_result_string(), "0-1");
+    }
+
+    #[test]
+    fn export_game_from_fen() {
+        let fen = "4k3/8/8/8/8/8/4p3/K7 b - - 0 40";
+        let mut game: Game<BitBoard> = Game::from_fen(fen).unwrap();
+        let mut board = BitBoard::from_fen(fen).unwrap();
+        let illegal_move = move_from_san(&board, "Kd7", constants()).unwrap();
+
+        assert_eq!(play(&mut game, &mut board, "e1=Q+", None), EndType::NoEnd);
+        assert_eq!(game.play_move(illegal_move, None), Err(ChessError::InvalidMove));
+
+        let pgn = game.get_record().to_pgn(false);
+        assert!(pgn.contains("[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4p3/K7 b - - 0 40\"]\n"));
+        assert!(pgn.ends_with("\n40... e1=Q+ *\n"));
+    }
+
+    #[test]
+    fn movetext_is_wrapped() {
+        let mut record = PgnGame::new();
+        record.moves = (0..40).map(|_| PgnMove::new("Nf3".to_string(), Some("a long comment".to_string()))).collect();
+
+        let pgn = record.to_pgn(true);
+        assert!(pgn.lines().all(|line| line.len() < 80));
+        assert_eq!(pgn.split_whitespace().filter(|word| *word == "Nf3").count(), 40);
+    }
+
+    #[test]
+    fn dates_and_clocks() {
+        let date = date_today();
+        assert_eq!((date.len(), &date[4..5], &date[7..8]), (10, ".", "."));
+        assert!(date[..4].parse::<u32>().unwrap() >= 2024);
+
+        assert_eq!(clock_string(Duration::from_secs(3725)), "1:02:05");
+        assert_eq!(clock_string(Duration::from_millis(3999)), "0:00:03");
+    }
+
+    #[test]
+    fn parse_games() {
+        let games = parse_pgn(GAMES).unwrap();
+        assert_eq!(games.len(), 3);
+
+        let game = &games[0];
+        assert_eq!(game.get_tag("Event"), Some("Reference \"games\""));
+        assert_eq!(game.get_tag("Site"), None);
+        assert_eq!((game.result.as_str(), game.comment.as_deref()), ("1-0", Some("Opening comment")));
+
+        let sans: Vec<&str> = game.moves.iter().map(|mov| mov.san.as_str()).collect();
+        assert_eq!(sans, ["e4", "e5", "Nf3", "Nc6", "Bb5", "a6", "Ba4"]);
+        assert_eq!((&game.moves[3].nags, game.moves[3].comment.as_deref()), (&vec![1, 14], Some("Developing; the knight")));
+        assert_eq!((&game.moves[5].nags, game.moves[5].comment.as_deref()), (&vec![6], Some("rest of the line")));
+
+        // Variations are kept with the move they replace, also inside other variations
+        let variation = &game.moves[2].variations[0];
+        assert_eq!(variation.iter().map(|mov| mov.san.as_str()).collect::<Vec<&str>>(), ["f4", "exf4", "Nf3"]);
+        assert_eq!(variation[1].variations[0][0].san, "d5");
+
+        assert_eq!((games[1].get_tag("Event"), games[1].moves.len(), games[1].result.as_str()), (Some("No result [A]"), 2, "*"));
+
+        let castling = &parse_pgn("1. 0-0 0-0-0! *").unwrap()[0];
+        assert_eq!(castling.moves.iter().map(|mov| mov.san.as_str()).collect::<Vec<&str>>(), ["0-0", "0-0-0"]);
+        assert_eq!(castling.moves[1].nags, vec![1]);
+
+        // "e.p." after an en passant capture, on its own or not
+        let en_passant = &parse_pgn("1. e4 Nf6 2. e5 d5 3. exd6 e.p. e6 4. d4 b6 5. d5 c5 6. dxc6e.p. *").unwrap()[0];
+        assert_eq!(en_passant.moves.iter().map(|mov| mov.san.as_str()).collect::<Vec<&str>>(),
+                   ["e4", "Nf6", "e5", "d5", "exd6", "e6", "d4", "b6", "d5", "c5", "dxc6e.p."]);
+        let positions: Vec<BitBoard> = en_passant.replay(constants()).unwrap();
+        assert_eq!(positions[11].to_fen(), "rnbqkb1r/p4ppp/1pPPpn2/8/8/8/PPP2PPP/RNBQKBNR b KQkq - 0 6");
+
+        assert!(parse_pgn("1. e4 (e5").is_err());
+        assert!(parse_pgn("1. e4 {never closed").is_err());
+        assert!(parse_pgn("[Event \"never closed] 1. e4").is_err());
+        assert!(parse_pgn("1. e4 e5)").is_err());
+    }
+
+    #[test]
+    fn export_and_parse_round_trip() {
+        let game = &parse_pgn(GAMES).unwrap()[0];
+        let pgn = game.to_pgn(true);
+
+        assert!(pgn.contains("{Opening comment} 1. e4 e5 2. Nf3 (2. f4 exf4 (2... d5) 3. Nf3) 2... Nc6 $1 $14"));
+        assert_eq!(&parse_pgn(&pgn).unwrap()[0], game);
+    }
+
+    #[test]
+    fn replay_games() {
+        let games = parse_pgn(GAMES).unwrap();
+
+        let positions: Vec<BitBoard> = games[0].replay(constants()).unwrap();
+        assert_eq!(positions.len(), 8);
+        assert_eq!(positions[7].to_fen(), "r1bqkbnr/1ppp1ppp/p1n5/4p3/B3P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 1 4");
+
+        // The king and rook are back on the squares they castled to, but castling again is not allowed
+        let err = games[2].replay::<BitBoard>(constants()).unwrap_err();
+        assert_eq!(err, PgnError { ply: 7, san: "O-O".to_string(), error: ChessError::InvalidMove });
+        assert_eq!(games[2].replay::<NormalBoard>(constants()).unwrap_err(), err);
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod search_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::evaluation_functions::board_piece_evaluation::board_piece_evaluation;
+    use crate::search::transposition_table::TranspositionTable;
+    use crate::search::search_options::SearchOptions;
+    use crate::search::move_ordering::MoveOrdering;
+    use crate::enums::piece_color::PieceColor;
+    use crate::evaluation_functions::score::mate_score;
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::turn_functions::minimax_move::{search, search_window, SearchResult};
+    use crate::search::iterative_deepening::{SearchLimits, SearchParams};
+    use crate::enums::chess_error::ChessError;
+    use crate::notation::line_to_san;
+    use crate::tests::unit_tests::common::constants;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    // Runs search_fn to the given depth with a new transposition table
+    fn run_search<F>(board: &BitBoard, depth: i32, options: &SearchOptions, search_fn: F) -> SearchResult
+        where F: FnOnce(&SearchParams<BitBoard>, &mut MoveOrdering) -> Result<SearchResult, ChessError> {
+
+        let params = SearchParams {
+            board,
+            history: &[],
+            limits: SearchLimits { depth, time: None },
+            eval_func: board_piece_evaluation,
+            constants: constants(),
+            options,
+            tt: &Arc::new(TranspositionTable::new(1)),
+            stop: &AtomicBool::new(false)
+        };
+        search_fn(&params, &mut MoveOrdering::new()).unwrap()
+    }
+
+    #[test]
+    fn captures_are_played_out_past_the_depth() {
+        // The pawn on d5 is defended, so taking it with the queen loses the queen
+        let board = BitBoard::from_fen("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1").unwrap();
+
+        for alpha_beta_pruning in [false, true] {
+            let res = run_search(&board, 1, &SearchOptions { alpha_beta_pruning, ..SearchOptions::default() }, search);
+            assert_ne!(res.best_move.to_uci(), "d1d5");
+            assert_eq!(res.score, 700);
+        }
+
+        // Black to move can take the undefended queen, so standing pat is not enough
+        let board = BitBoard::from_fen("4k3/8/8/3r4/8/8/8/3QK3 b - - 0 1").unwrap();
+        let res = run_search(&board, 1, &SearchOptions::default(), search);
+        assert_eq!((res.best_move.to_uci(), res.score), ("d5d1".to_string(), 0));
+    }
+
+    #[test]
+    fn ordering_puts_the_likely_cutoffs_first() {
+        let board = BitBoard::from_fen("4k3/8/3r4/1q6/2P5/2N5/8/4K2R w K - 0 1").unwrap();
+        let mut moves = board.generate_moves(constants()).unwrap();
+        let find = |moves: &[_], uci: &str| moves.iter().copied().find(|mov: &crate::board_types::chess_move::Move| mov.to_uci() == uci).unwrap();
+
+        let hash_move = find(&moves, "h1h7");
+        let killer = find(&moves, "e1f2");
+        let mut ordering = MoveOrdering::new();
+        ordering.add_cutoff(killer, 3, 2, PieceColor::White);
+        ordering.order_moves(&mut moves, Some(hash_move.key()), 3, PieceColor::White, true);
+
+        let order: Vec<String> = moves.iter().take(4).map(|mov| mov.to_uci()).collect();
+        // The pawn takes the queen before the knight does
+        assert_eq!(order, vec!["h1h7", "c4b5", "c3b5", "e1f2"]);
+    }
+
+    #[test]
+    fn ordering_keeps_the_score_and_searches_fewer_nodes() {
+        let board = BitBoard::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+
+        let [unordered, ordered] = [false, true].map(|move_ordering| {
+            let options = SearchOptions { move_ordering, ..SearchOptions::full_width() };
+            run_search(&board, 3, &options, search)
+        });
+
+        assert_eq!(unordered.score, ordered.score);
+        assert!(ordered.nodes < unordered.nodes, "{} >= {}", ordered.nodes, unordered.nodes);
+        assert!(ordered.first_move_cutoffs * unordered.cutoffs > unordered.first_move_cutoffs * ordered.cutoffs);
+    }
+
+    #[test]
+    fn principal_variation_is_a_legal_line() {
+        let board = BitBoard::from_fen("4k3/8/3r4/8/3P4/2N5/8/4K3 w - - 0 1").unwrap();
+
+        for alpha_beta_pruning in [false, true] {
+            let options = SearchOptions { alpha_beta_pruning, ..SearchOptions::full_width() };
+            let res = run_search(&board, 3, &options, search);
+
+            assert_eq!(res.pv.len(), 3);
+            assert_eq!(res.pv[0], res.best_move);
+            assert!(line_to_san(&board, &res.pv, constants()).is_ok());
+        }
+    }
+
+    #[test]
+    fn scores_outside_the_window_are_bounds() {
+        // Black to move, so the window is turned around inside the search
+        let board = BitBoard::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 3 3").unwrap();
+        let options = SearchOptions::full_width();
+        let search_with = |alpha, beta| run_search(&board, 3, &options, |params, ordering| search_window(params, alpha, beta, ordering));
+
+        let score = run_search(&board, 3, &options, search).score;
+
+        assert_eq!(search_with(score - 10, score + 10).score, score);
+        assert!(search_with(score + 10, score + 100).score <= score + 10);
+        assert!(search_with(score - 100, score - 10).score >= score - 10);
+    }
+
+    #[test]
+    fn repetitions_are_draws() {
+        // Black is a queen down, but can go back to a position from earlier in the game
+        let board = BitBoard::from_fen("3k4/8/8/8/8/8/8/Q3K3 b - - 4 9").unwrap();
+        let repeated = BitBoard::from_fen("4k3/8/8/8/8/8/8/Q3K3 w - - 5 10").unwrap();
+        let stop = AtomicBool::new(false);
+        let search_after = |history: &[u64]| {
+            let params = SearchParams {
+                board: &board,
+                history,
+                limits: SearchLimits { depth: 3, time: None },
+                eval_func: board_piece_evaluation,
+                constants: constants(),
+                options: &SearchOptions::default(),
+                tt: &Arc::new(TranspositionTable::new(1)),
+                stop: &stop
+            };
+            search(&params, &mut MoveOrdering::new()).unwrap()
+        };
+
+        assert!(search_after(&[]).score > 500);
+        let res = search_after(&[repeated.get_hash()]);
+        assert_eq!((res.best_move.to_uci(), res.score), ("d8e8".to_string(), 0));
+    }
+
+    // The full width options with one of the selective techniques turned on
+    fn selective_options() -> [SearchOptions; 4] {
+        let full_width = SearchOptions::full_width();
+        [SearchOptions { null_move_pruning: true, ..full_width },
+         SearchOptions { late_move_reductions: true, ..full_width },
+         SearchOptions { futility_pruning: true, ..full_width },
+         SearchOptions { check_extensions: true, ..full_width }]
+    }
+
+    #[test]
+    fn selective_search_searches_fewer_nodes() {
+        let board = BitBoard::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3").unwrap();
+        let nodes = |options: &SearchOptions| run_search(&board, 4, options, search).nodes;
+
+        let full_width = nodes(&SearchOptions::full_width());
+        assert!(nodes(&SearchOptions::default()) < full_width);
+        // Extending checks searches more, the others prune
+        for options in &selective_options()[..3] {
+            assert!(nodes(options) < full_width, "{:?}", options);
+        }
+    }
+
+    #[test]
+    fn selective_search_still_finds_mates() {
+        for (fen, winner) in [("r5k1/5ppp/8/8/8/8/4RPPP/4R1K1 w - - 0 1", PieceColor::White),
+                              ("4r1k1/4rppp/8/8/8/8/5PPP/R5K1 b - - 0 1", PieceColor::Black)] {
+            let board = BitBoard::from_fen(fen).unwrap();
+
+            for options in selective_options().iter().chain([SearchOptions::default()].iter()) {
+                let res = run_search(&board, 4, options, search);
+                assert_eq!(res.score, mate_score(winner, 3), "{} with {:?}", fen, options);
+            }
+        }
+    }
+}
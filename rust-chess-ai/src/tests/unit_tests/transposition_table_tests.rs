@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod transposition_table_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::evaluation_functions::board_piece_evaluation::board_piece_evaluation;
+    use crate::search::transposition_table::{TranspositionTable, Bound};
+    use crate::search::search_options::SearchOptions;
+    use crate::search::move_ordering::MoveOrdering;
+    use crate::search::iterative_deepening::{SearchLimits, SearchParams};
+    use crate::turn_functions::minimax_move::{search, SearchResult};
+    use crate::tests::unit_tests::common::constants;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    #[test]
+    fn store_and_probe() {
+        let tt = TranspositionTable::new(1);
+        assert_eq!(tt.probe(12345), None);
+
+        tt.store(12345, 4, -250, Bound::Lower, Some(0x1234));
+        let entry = tt.probe(12345).unwrap();
+        assert_eq!((entry.depth, entry.score, entry.bound, entry.best_move), (4, -250, Bound::Lower, Some(0x1234)));
+
+        tt.store(12345, 5, 30, Bound::Exact, None);
+        let entry = tt.probe(12345).unwrap();
+        assert_eq!((entry.depth, entry.score, entry.bound, entry.best_move), (5, 30, Bound::Exact, None));
+
+        tt.clear();
+        assert_eq!(tt.probe(12345), None);
+    }
+
+    #[test]
+    fn deeper_entries_are_kept() {
+        let tt = TranspositionTable::new(1);
+        // Same bucket, different keys
+        let (a, b, c) = (1, 1 + (1 << 40), 1 + (2 << 40));
+
+        tt.store(a, 6, 0, Bound::Exact, None);
+        tt.store(b, 2, 0, Bound::Exact, None);
+        assert_eq!(tt.probe(a).unwrap().depth, 6);
+        assert_eq!(tt.probe(b).unwrap().depth, 2);
+
+        // A shallow entry only replaces the always-replace slot
+        tt.store(c, 1, 0, Bound::Exact, None);
+        assert!(tt.probe(a).is_some());
+        assert!(tt.probe(b).is_none());
+
+        // Entries from an earlier search can be replaced by anything
+        tt.new_search();
+        tt.store(b, 1, 0, Bound::Exact, None);
+        assert!(tt.probe(a).is_none());
+        assert!(tt.probe(b).is_some());
+    }
+
+    #[test]
+    fn size_is_a_power_of_two() {
+        assert_eq!(TranspositionTable::new(1).size_mb(), 1);
+        assert_eq!(TranspositionTable::new(3).size_mb(), 2);
+        assert_eq!(TranspositionTable::new(64).size_mb(), 64);
+    }
+
+    #[test]
+    fn search_result_does_not_change() {
+        let board = BitBoard::from_fen("4k3/8/3r4/8/3P4/2N5/8/4K3 w - - 0 1").unwrap();
+        let stop = AtomicBool::new(false);
+        let search_with = |options: &SearchOptions, tt: &Arc<TranspositionTable>| -> SearchResult {
+            let params = SearchParams {
+                board: &board,
+                history: &[],
+                limits: SearchLimits { depth: 3, time: None },
+                eval_func: board_piece_evaluation,
+                constants: constants(),
+                options,
+                tt,
+                stop: &stop
+            };
+            search(&params, &mut MoveOrdering::new()).unwrap()
+        };
+
+        let no_pruning = search_with(&SearchOptions { alpha_beta_pruning: false, ..SearchOptions::full_width() }, &Arc::new(TranspositionTable::new(1)));
+
+        // A second search reuses the entries from the first one
+        let tt = Arc::new(TranspositionTable::new(1));
+        for _ in 0..2 {
+            tt.new_search();
+            let res = search_with(&SearchOptions::full_width(), &tt);
+            assert_eq!(res.score, no_pruning.score);
+        }
+    }
+}
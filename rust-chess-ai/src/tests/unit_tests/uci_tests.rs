@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod uci_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::enums::{
+        chess_error::ChessError,
+        evaluation_type::EvaluationType,
+        piece_color::PieceColor
+    };
+    use crate::search::iterative_deepening::{SearchLimits, MAX_DEPTH};
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::uci::{GoCommand, parse_position, parse_go, parse_hash_option, parse_threads_option, parse_evaluation_option};
+    use crate::tests::unit_tests::common::constants;
+    use std::time::Duration;
+
+    fn tokens(command: &str) -> Vec<&str> {
+        command.split_whitespace().collect()
+    }
+
+    fn go(command: &str, turn: PieceColor) -> GoCommand {
+        parse_go(&tokens(command), turn)
+    }
+
+    #[test]
+    fn go_with_a_clock() {
+        // A 30th of the time left and half of the increment, minus the 50 ms kept for sending the move
+        assert_eq!(go("wtime 300000 btime 1000 winc 4000", PieceColor::White).limits.time, Some(Duration::from_millis(11_950)));
+        assert_eq!(go("wtime 60000 btime 60000 movestogo 10", PieceColor::Black).limits.time, Some(Duration::from_millis(5_950)));
+        // Even with almost no time left there is some time to search
+        assert_eq!(go("wtime 300000 btime 100", PieceColor::Black).limits.time, Some(Duration::from_millis(1)));
+        // Sent by some GUIs when the clock has run out
+        assert_eq!(go("wtime -100 btime 100 winc 0 binc 0", PieceColor::White).limits.time, Some(Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn go_with_other_limits() {
+        assert_eq!(go("movetime 1000", PieceColor::White), GoCommand {
+            limits: SearchLimits { depth: MAX_DEPTH, time: Some(Duration::from_millis(950)) },
+            infinite: false
+        });
+        assert_eq!(go("depth 5", PieceColor::White), GoCommand {
+            limits: SearchLimits { depth: 5, time: None },
+            infinite: false
+        });
+
+        // Limits the engine doesn't understand still get a move in the end
+        for command in ["nodes 100000", "mate 3", "wtime lots", ""] {
+            assert_eq!(go(command, PieceColor::White).limits, SearchLimits { depth: MAX_DEPTH, time: Some(Duration::from_millis(4_950)) }, "{}", command);
+        }
+
+        // The clock doesn't matter when searching until stop
+        let infinite = GoCommand {
+            limits: SearchLimits { depth: MAX_DEPTH, time: None },
+            infinite: true
+        };
+        assert_eq!(go("infinite", PieceColor::White), infinite);
+        assert_eq!(go("wtime 1000 btime 1000 infinite", PieceColor::Black), infinite);
+    }
+
+    #[test]
+    fn positions_with_moves() {
+        let history = parse_position(&tokens("startpos moves e2e4 e7e5 g1f3"), constants()).unwrap();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[0], BitBoard::new_board());
+        assert_eq!(history[3].to_fen(), "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2");
+
+        let history = parse_position(&tokens("fen 4k3/P7/8/8/8/8/8/4K3 w - - 0 1 moves a7a8q"), constants()).unwrap();
+        assert_eq!(history[1].to_fen(), "Q3k3/8/8/8/8/8/8/4K3 b - - 0 1");
+        // A promotion always has the piece in UCI
+        assert_eq!(parse_position(&tokens("fen 4k3/P7/8/8/8/8/8/4K3 w - - 0 1 moves a7a8"), constants()), Err(ChessError::InvalidMove));
+
+        assert_eq!(parse_position(&tokens("startpos moves e2e4 e7e4"), constants()), Err(ChessError::InvalidMove));
+        assert_eq!(parse_position(&tokens("startpos moves e2e4 e7"), constants()), Err(ChessError::InvalidMoveString));
+        assert!(parse_position(&tokens("fen 4k3/8/8 w - - 0 1"), constants()).is_err());
+        assert!(parse_position(&tokens("moves e2e4"), constants()).is_err());
+    }
+
+    #[test]
+    fn options() {
+        assert_eq!(parse_hash_option(&tokens("name Hash value 128")), Some(128));
+        assert_eq!(parse_hash_option(&tokens("name hash value lots")), None);
+        assert_eq!(parse_hash_option(&tokens("name Threads value 4")), None);
+
+        assert_eq!(parse_threads_option(&tokens("name Threads value 4")), Some(4));
+        assert_eq!(parse_threads_option(&tokens("name Threads value")), None);
+
+        assert_eq!(parse_evaluation_option(&tokens("name Evaluation value material")), Some(EvaluationType::Material));
+        assert_eq!(parse_evaluation_option(&tokens("name Evaluation value PieceSquare")), Some(EvaluationType::PieceSquare));
+        assert_eq!(parse_evaluation_option(&tokens("name Evaluation value Neural")), None);
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod zobrist_unit_tests {
+    use crate::board_types::bitboard::BitBoard;
+    use crate::board_types::normalboard::NormalBoard;
+    use crate::board_types::position::START_FEN;
+    use crate::traits::chess_board_contract::ChessBoardContract;
+    use crate::notation::move_from_uci;
+    use crate::tests::unit_tests::common::constants;
+
+    fn play(board: &BitBoard, moves: &[&str]) -> BitBoard {
+        moves.iter().fold(*board, |board, mov| {
+            board.after_move(move_from_uci(&board, mov, constants()).unwrap()).unwrap()
+        })
+    }
+
+    fn check_incremental_hashes(board: &BitBoard, depth: u32) {
+        if depth == 0 {
+            return;
+        }
+
+        for mov in board.generate_moves(constants()).unwrap() {
+            let new_board = board.after_move(mov).unwrap();
+            assert_eq!(new_board.hash, new_board.compute_hash(), "{} after {}", board.to_fen(), mov.to_uci());
+            check_incremental_hashes(&new_board, depth - 1);
+        }
+    }
+
+    #[test]
+    fn incremental_hash_matches_full_hash() {
+        // Start position, Kiwipete (castling), and positions with en passant and promotions
+        let fens = [
+            START_FEN,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1"
+        ];
+
+        for fen in fens {
+            check_incremental_hashes(&BitBoard::from_fen(fen).unwrap(), 2);
+        }
+    }
+
+    #[test]
+    fn transpositions_have_equal_hashes() {
+        let start = BitBoard::from_fen(START_FEN).unwrap();
+
+        let a = play(&start, &["g1f3", "g8f6", "b1c3"]);
+        let b = play(&start, &["b1c3", "g8f6", "g1f3"]);
+        assert_eq!(a.hash, b.hash);
+
+        // Moving the knights back and forth repeats the start position
+        let c = play(&start, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+        assert_eq!(c.hash, start.hash);
+    }
+
+    #[test]
+    fn hash_depends_on_position_state() {
+        let hash = |fen: &str| BitBoard::from_fen(fen).unwrap().hash;
+
+        let base = hash("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq - 0 1");
+        assert_ne!(base, hash("r3k2r/8/8/3pP3/8/8/8/R3K2R b KQkq - 0 1"));
+        assert_ne!(base, hash("r3k2r/8/8/3pP3/8/8/8/R3K2R w Kkq - 0 1"));
+        assert_ne!(base, hash("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1"));
+        // The clocks are not part of the hash
+        assert_eq!(base, hash("r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq - 10 30"));
+    }
+
+    #[test]
+    fn normalboard_hash_matches_bitboard() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        assert_eq!(NormalBoard::from_fen(fen).unwrap().get_hash(), BitBoard::from_fen(fen).unwrap().get_hash());
+    }
+}
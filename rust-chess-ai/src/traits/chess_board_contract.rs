@@ -1,13 +1,104 @@
 use crate::enums::chess_error::ChessError;
 use crate::enums::end_type::EndType;
+use crate::enums::tie_type::TieType;
+use crate::enums::piece_type::PieceType;
 use crate::{enums::piece_color::PieceColor, board_types::bitboard::Constants};
+use crate::board_types::chess_move::Move;
 use std::sync::Arc;
 
 
-pub trait ChessBoardContract where Self: Sized {
-    fn generate_moves(&self, prev_board: Option<&Self>, turn: PieceColor, constants: &Constants) -> Result<Vec<(String, Self)>, ChessError>;
-    fn check_game_end(&self, prev_board: Option<&Self>, turn: PieceColor, constants: &Constants) -> Result<EndType, ChessError>;
+pub trait ChessBoardContract where Self: Sized + Clone {
+    // What make_move returns to take the move back with unmake_move
+    type Undo;
+
+    // The legal moves of the player whose turn it is
+    fn generate_moves(&self, constants: &Constants) -> Result<Vec<Move>, ChessError>;
+    // Plays a move from generate_moves on this board
+    fn make_move(&mut self, mov: Move) -> Result<Self::Undo, ChessError>;
+    fn unmake_move(&mut self, mov: Move, undo: Self::Undo);
+    // Passes the turn without moving, which the null-move pruning of the search uses. Not for
+    // positions where the player whose turn it is is in check
+    fn make_null_move(&mut self) -> Self::Undo;
+    fn unmake_null_move(&mut self, undo: Self::Undo);
+    // Whether the king of the player whose turn it is is attacked
+    fn in_check(&self, constants: &Constants) -> bool;
+    fn check_game_end(&self, constants: &Constants) -> Result<EndType, ChessError>;
     fn get_value_of_pieces(&self, piece_values: [i32; 6]) -> i32;
+    // The placement scores are from white's point of view and indexed [letter][number] for every
+    // piece type. Black's pieces are scored on the square mirrored to their side of the board
+    fn get_value_of_placements(&self, placement_scores: &[[[i32; 8]; 8]; 6]) -> i32;
+    // Of both colors together
+    fn get_piece_count(&self, typ: PieceType) -> u32;
+    // Whether the color has any pieces besides its king and pawns
+    fn has_non_pawn_material(&self, color: PieceColor) -> bool;
+    fn get_turn(&self) -> PieceColor;
+    fn get_halfmove_clock(&self) -> u32;
+    fn get_fullmove_number(&self) -> u32;
+    fn get_hash(&self) -> u64;
     fn new_board() -> Self;
+    fn from_fen(fen: &str) -> Result<Self, ChessError>;
+    fn to_fen(&self) -> String;
     fn board_ascii(&self, use_unicode: bool) -> String;
+
+    // The legal captures and promotions, in the same order as in generate_moves. Boards that can
+    // generate them without the other moves should do so, as the quiescence search calls this a lot
+    fn generate_captures(&self, constants: &Constants) -> Result<Vec<Move>, ChessError> {
+        Ok(self.generate_moves(constants)?
+               .into_iter()
+               .filter(|mov| mov.is_capture() || mov.promotion().is_some())
+               .collect())
+    }
+
+    // Counts the leaf nodes of the move tree, for checking the move generation against known counts
+    fn perft(&self, depth: u32, constants: &Constants) -> Result<u64, ChessError> {
+        if depth == 0 {
+            return Ok(1);
+        }
+
+        let moves = self.generate_moves(constants)?;
+        if depth == 1 {
+            return Ok(moves.len() as u64);
+        }
+
+        let mut board = self.clone();
+        let mut nodes = 0;
+        for mov in moves {
+            let undo = board.make_move(mov)?;
+            nodes += board.perft(depth - 1, constants)?;
+            board.unmake_move(mov, undo);
+        }
+
+        Ok(nodes)
+    }
+
+    // Perft split up by the first move, to narrow down where a count goes wrong
+    fn divide(&self, depth: u32, constants: &Constants) -> Result<Vec<(Move, u64)>, ChessError> {
+        self.generate_moves(constants)?
+            .into_iter()
+            .map(|mov| Ok((mov, self.after_move(mov)?.perft(depth.saturating_sub(1), constants)?)))
+            .collect()
+    }
+
+    // A copy of the board with the move played on it
+    fn after_move(&self, mov: Move) -> Result<Self, ChessError> {
+        let mut board = self.clone();
+        board.make_move(mov)?;
+        Ok(board)
+    }
+
+    // Threefold repetition, where board_history holds the positions before this one. Positions from
+    // before the last capture or pawn move can not come back, so only those after it are compared
+    fn check_repetition(&self, board_history: &[Self]) -> EndType {
+        let hash = self.get_hash();
+        let repetitions = board_history.iter()
+                                       .rev()
+                                       .take(self.get_halfmove_clock() as usize)
+                                       .filter(|board| board.get_hash() == hash)
+                                       .count();
+
+        match repetitions >= 2 {
+            true => EndType::Tie(TieType::ThreefoldRepetition),
+            false => EndType::NoEnd
+        }
+    }
 }
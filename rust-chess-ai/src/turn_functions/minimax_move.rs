@@ -1,195 +1,533 @@
 use crate::board_types::bitboard::Constants;
+use crate::board_types::chess_move::Move;
+use crate::notation::{move_to_san, line_to_san};
+use crate::evaluation_functions::score::{score_to_tt, score_from_tt, is_mate_score, INFINITY};
 use crate::traits::chess_board_contract::ChessBoardContract;
-use crate::{Player, EvaluationFunction};
-
-use crate::enums::{
-    piece_color::PieceColor,
-    chess_error::ChessError
-};
-
-pub fn minimax_move<T: ChessBoardContract + Clone + Send + Sync>(board: &T,
-                                                                 prev_board: Option<&T>,
-                                                                 board_history: &Vec<T>,
-                                                                 turn: PieceColor,
-                                                                 player: &Player<T>,
-                                                                 eval_func: EvaluationFunction<T>,
-                                                                 constants: &Constants,
-                                                                 alpha_beta_pruning: bool,
-                                                                 multi_threading: bool) -> Result<String, ChessError> {
-
-    println!("Looking {} moves ahead...", player.moves_ahead);
-    let start_time = std::time::Instant::now();
+use crate::search::transposition_table::{TranspositionTable, Bound};
+use crate::search::iterative_deepening::{iterative_deepening, SearchLimits, SearchParams};
+use crate::search::search_options::SearchOptions;
+use crate::search::search_threads::SearchThreads;
+use crate::search::move_ordering::{MoveOrdering, is_tactical, order_captures};
+use crate::EvaluationFunction;
 
-    let possible_moves = board.generate_moves(prev_board, turn, constants)?;
-
-    let evaluated_moves: Vec<(i32, String)> = match multi_threading {
-        false => {
-
-            possible_moves.iter().map(|(mov_str, mov_board)| -> Result<(i32, String), ChessError> {
-
-                let mut new_board_history = board_history.clone();
-                new_board_history.push(mov_board.clone());
-        
-                let eval = minimax_move_helper(
-                    mov_board,
-                    Some(&board),
-                    turn.opposite_color(),
-                    eval_func,
-                    &new_board_history,
-                    constants,
-                    player.moves_ahead - 1,
-                    i32::MIN,
-                    i32::MAX,
-                    alpha_beta_pruning
-                );
-
-                match eval {
-                    Ok(res) => {
-                        Ok((res, mov_str.clone()))
-                    },
-                    Err(err) => Err(err)
-                }
-        
-            }).collect()
+use crate::enums::chess_error::ChessError;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+// The null move is searched this much shallower than a real move would be, and only where that
+// leaves something to search
+const NULL_MOVE_REDUCTION: i32 = 2;
+const NULL_MOVE_MIN_DEPTH: i32 = 3;
+// Moves after this many in a position are searched one move less deep, or two after twice as
+// many. Not close to the leaves, where there is little left to save
+const LATE_MOVE_INDEX: usize = 3;
+const LATE_MOVE_MIN_DEPTH: i32 = 3;
+// How far below alpha the evaluation has to be for quiet moves to be skipped, by remaining depth
+const FUTILITY_MARGINS: [i32; 3] = [0, 200, 500];
+// Checks are only extended this many times the depth of the search from the root, so a long
+// series of checks doesn't make the search take forever
+const CHECK_EXTENSION_PLIES: i32 = 2;
+
+pub struct SearchResult {
+    pub best_move: Move,
+    // From white's point of view, like the evaluation functions
+    pub score: i32,
+    pub depth: i32,
+    // The best move and the replies the engine expects after it, as far as it searched
+    pub pv: Vec<Move>,
+    pub nodes: u64,
+    // How many moves caused a cutoff, and how many of those were the first move searched in
+    // their position, which shows how well the moves are ordered
+    pub cutoffs: u64,
+    pub first_move_cutoffs: u64
+}
+
+// Shared between the threads
+#[derive(Default)]
+struct SearchStats {
+    nodes: AtomicU64,
+    cutoffs: AtomicU64,
+    first_move_cutoffs: AtomicU64
+}
 
-        },
-        true => {
+impl SearchStats {
+    fn add_cutoff(&self, first_move: bool) {
+        self.cutoffs.fetch_add(1, Ordering::Relaxed);
+        if first_move {
+            self.first_move_cutoffs.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+// A root move with the line after it. The score is only a bound if it isn't exact
+struct RootMove {
+    score: i32,
+    pv: Vec<Move>,
+    exact: bool
+}
+
+// Searches the last position of board_history, where the earlier ones are needed to tell repetitions
+pub fn minimax_move<T: 'static + ChessBoardContract + Send + Sync>(board_history: &[T],
+                                                                   limits: &SearchLimits,
+                                                                   eval_func: EvaluationFunction<T>,
+                                                                   constants: &Constants,
+                                                                   options: &SearchOptions,
+                                                                   tt: &Arc<TranspositionTable>,
+                                                                   helpers: Option<&SearchThreads>) -> Result<SearchResult, ChessError> {
 
-            let thread_num = num_cpus::get();
-            let queue: work_queue::Queue<(String, T)> = work_queue::Queue::new(thread_num, 128);
+    match limits.time {
+        Some(time) => println!("Thinking for up to {} seconds...", time.as_millis() as f32 / 1000.),
+        None => println!("Looking {} moves ahead...", limits.depth)
+    }
+    let start_time = std::time::Instant::now();
 
-            for mov in possible_moves {
-                queue.push(mov.clone());
+    let (board, earlier_boards) = board_history.split_last().ok_or(ChessError::NoMovesFound)?;
+    let history: Vec<u64> = earlier_boards.iter().map(|board| board.get_hash()).collect();
+
+    let stop = AtomicBool::new(false);
+    let params = SearchParams {
+        board,
+        history: &history,
+        limits: *limits,
+        eval_func,
+        constants,
+        options,
+        tt,
+        stop: &stop
+    };
+    let res = iterative_deepening(&params, helpers, |_, _| ())?;
+
+    // Only happens if the time runs out before the first iteration is done, in which case the
+    // score is just the evaluation of the current position
+    let res = match res {
+        Some(res) => res,
+        None => {
+            let best_move = *board.generate_moves(constants)?.first().ok_or(ChessError::NoMovesFound)?;
+            SearchResult {
+                best_move,
+                score: eval_func(board, 0, constants)?,
+                depth: 0,
+                pv: vec![best_move],
+                nodes: 0,
+                cutoffs: 0,
+                first_move_cutoffs: 0
             }
+        }
+    };
+
+    println!("Finished in {} seconds, making the following move: {}", start_time.elapsed().as_millis() as f32 / 1000., move_to_san(board, res.best_move, constants)?);
+    println!("Expecting: {}", line_to_san(board, &res.pv, constants)?.join(" "));
+    if let Some(percentage) = (res.first_move_cutoffs * 100).checked_div(res.cutoffs) {
+        println!("Searched {} nodes to depth {}, {}% of the cutoffs on the first move", res.nodes, res.depth, percentage);
+    }
+    Ok(res)
+}
+
+// Searches limits.depth moves ahead. Setting stop makes the search return
+// ChessError::SearchStopped as soon as possible. The killer moves and history in ordering are
+// kept for the next search of the same thread
+pub fn search<T: ChessBoardContract>(params: &SearchParams<T>, ordering: &mut MoveOrdering) -> Result<SearchResult, ChessError> {
+    search_window(params, -INFINITY, INFINITY, ordering)
+}
+
+// Like search, but only looks for scores between alpha and beta, which are from white's point of
+// view like the score. A score of alpha or lower means the real score is at most that, and a
+// score of beta or higher that it is at least that
+pub fn search_window<T: ChessBoardContract>(params: &SearchParams<T>,
+                                            alpha: i32,
+                                            beta: i32,
+                                            ordering: &mut MoveOrdering) -> Result<SearchResult, ChessError> {
+
+    let SearchParams { board, history, eval_func, constants, options, tt, stop, .. } = *params;
+    let depth = params.limits.depth;
+
+    // The search itself scores from the point of view of the side to move
+    let side_const = board.get_turn().side_const();
+    let (alpha, beta) = match side_const {
+        1 => (alpha, beta),
+        _ => (-beta, -alpha)
+    };
+
+    let context = SearchContext {
+        eval_func,
+        constants,
+        options,
+        tt,
+        stop,
+        root_depth: depth,
+        stats: SearchStats::default()
+    };
+    let hash = board.get_hash();
 
-            std::thread::scope(|s| -> Result<Vec<(i32, String)>, ChessError> {
-
-                let handles = queue.local_queues().map(|mut local_queue| {
-                    let new_depth = player.moves_ahead - 1;
-                    s.spawn(move || {
-                        let mut results: Vec<Result<(i32, String), ChessError>> = Vec::new();
-
-                        while let Some((mov_str, mov_board)) = local_queue.pop() {
-                            let mut new_history = board_history.clone();
-                            new_history.push(mov_board.clone());
-
-                            let eval = minimax_move_helper(
-                                &mov_board,
-                                Some(&board),
-                                turn.opposite_color(),
-                                eval_func,
-                                &new_history,
-                                constants,
-                                new_depth,
-                                i32::MIN,
-                                i32::MAX,
-                                alpha_beta_pruning
-                            );
-
-                            match eval {
-                                Err(err) => results.push(Err(err)),
-                                Ok(val) => results.push(Ok((val, mov_str)))
-                            }
-                        }
-
-                        results
-                    })
-                }).collect::<Vec<_>>();
-
-                handles.into_iter()
-                    .map(|h| h.join().unwrap())
-                    .flatten()
-                    .fold(Ok(Vec::new()), |acc, item| {
-                    let mut acc_val: Vec<(i32, String)> = acc?;
-                    let item_val = item?;
-                    acc_val.push(item_val);
-
-                    Ok(acc_val)
-                })
-
-            })
-        }
-    }?;
-
-    let maximizing_player = turn == PieceColor::White;
-    let best_move = match maximizing_player {
-        true => evaluated_moves.iter().max_by_key(|(value, _)| value),
-        false => evaluated_moves.iter().min_by_key(|(value, _)| value),
+    // Searching the best move of the previous search first gives the other moves a tighter window
+    let mut state = SearchState {
+        ordering,
+        positions: history.to_vec()
     };
+    let mut move_order = board.generate_moves(constants)?;
+    state.ordering.order_moves(&mut move_order, tt.probe(hash).and_then(|entry| entry.best_move), 0, board.get_turn(), options.move_ordering);
+
+    // The other moves only have to prove that they are worse than the best one so far, instead
+    // of getting an exact score
+    let mut root_alpha = alpha;
+    let mut board = board.clone();
+    let mut evaluated_moves = Vec::with_capacity(move_order.len());
+
+    for (i, mov) in move_order.iter().copied().enumerate() {
+        let mut pv = Vec::new();
+        let node = Node { depth, ply: 0, alpha: root_alpha, beta };
+        let score = context.search_move(&mut board, mov, node, MoveSearch { null_window: i > 0, reduction: 0 }, &mut state, &mut pv)?;
+
+        pv.insert(0, mov);
+        evaluated_moves.push(RootMove {
+            score,
+            pv,
+            exact: !options.alpha_beta_pruning || (score > root_alpha && score < beta)
+        });
+        root_alpha = root_alpha.max(score);
 
-    match best_move {
-        Some((_, mov_str)) => {
-            println!("Finished in {} seconds, making the following move: {}", start_time.elapsed().as_millis() as f32 / 1000., mov_str);
-            Ok(mov_str.clone())
-        },
-        None => Err(ChessError::NoMovesFound)
+        // The score is already too high for the window, so the other moves don't matter
+        if options.alpha_beta_pruning && score >= beta {
+            break;
+        }
     }
+
+    // The move that first reached the best score was searched with a wider window, so it is
+    // exact. Moves that only tied with it may be worse
+    let best = evaluated_moves.into_iter()
+                              .max_by_key(|root_move| (root_move.score, root_move.exact))
+                              .ok_or(ChessError::NoMovesFound)?;
+
+    tt.store(hash, depth, best.score, window_bound(best.score, alpha, beta), Some(best.pv[0].key()));
+
+    Ok(SearchResult {
+        best_move: best.pv[0],
+        score: best.score * side_const,
+        depth,
+        pv: best.pv,
+        nodes: context.stats.nodes.load(Ordering::Relaxed),
+        cutoffs: context.stats.cutoffs.load(Ordering::Relaxed),
+        first_move_cutoffs: context.stats.first_move_cutoffs.load(Ordering::Relaxed)
+    })
+}
+
+// Whether the position was already reached since the last capture or pawn move
+fn is_repetition<T: ChessBoardContract>(board: &T, positions: &[u64]) -> bool {
+    let hash = board.get_hash();
+    positions.iter()
+             .rev()
+             .take(board.get_halfmove_clock() as usize)
+             .any(|position| *position == hash)
 }
 
-fn minimax_move_helper<T: ChessBoardContract + Clone>(board: &T,
-                                                      prev_board: Option<&T>,
-                                                      turn: PieceColor,
-                                                      eval_func: EvaluationFunction<T>,
-                                                      board_history: &Vec<T>,
-                                                      constants: &Constants,
-                                                      depth: i32,
-                                                      alpha: i32,
-                                                      beta: i32,
-                                                      alpha_beta_pruning: bool) -> Result<i32, ChessError> {
-    
-    let maximizing_player = turn == PieceColor::White;
-    let possible_moves = board.generate_moves(prev_board, turn, constants)?;
-    
-    if depth == 0 || possible_moves.len() == 0 {
-        return Ok(eval_func(board, prev_board, board_history, depth, constants)?);
+// Scores outside the window are only bounds on the real score
+fn window_bound(score: i32, alpha: i32, beta: i32) -> Bound {
+    if score <= alpha {
+        Bound::Upper
+    } else if score >= beta {
+        Bound::Lower
+    } else {
+        Bound::Exact
     }
+}
 
-    let mut ret_value = match maximizing_player {
-        true => i32::MIN,
-        false => i32::MAX
-    };
+// The depth left to search, the number of moves made since the root, which the mate scores
+// count from, and the window the score is needed in
+#[derive(Clone, Copy)]
+struct Node {
+    depth: i32,
+    ply: i32,
+    alpha: i32,
+    beta: i32
+}
+
+impl Node {
+    fn window(self, alpha: i32, beta: i32) -> Self {
+        Self { alpha, beta, ..self }
+    }
+}
+
+// Moves after the first are searched with a null window first, and late quiet moves at a
+// depth reduced by reduction
+#[derive(Clone, Copy)]
+struct MoveSearch {
+    null_window: bool,
+    reduction: i32
+}
+
+// What a search changes as it goes: the move ordering it learns and the hashes of the
+// positions before the current one, starting with the ones from the game
+struct SearchState<'a> {
+    ordering: &'a mut MoveOrdering,
+    positions: Vec<u64>
+}
+
+// Everything the search needs besides the position and the window. Scores are negamax scores,
+// from the point of view of the side to move, so a position scores the negated score of the
+// best position its moves lead to
+struct SearchContext<'a, T> {
+    eval_func: EvaluationFunction<T>,
+    constants: &'a Constants,
+    options: &'a SearchOptions,
+    tt: &'a TranspositionTable,
+    stop: &'a AtomicBool,
+    root_depth: i32,
+    stats: SearchStats
+}
+
+impl<T: ChessBoardContract> SearchContext<'_, T> {
+    fn evaluate(&self, board: &T, ply: i32) -> Result<i32, ChessError> {
+        Ok(board.get_turn().side_const() * (self.eval_func)(board, ply, self.constants)?)
+    }
+
+    // Plays the move, searches the position after it and takes the move back again, so the
+    // board is left unchanged. Returns the score for the side that made the move. Moves that give
+    // check are searched deeper with check_extensions and never reduced
+    fn search_move(&self,
+                   board: &mut T,
+                   mov: Move,
+                   node: Node,
+                   search: MoveSearch,
+                   state: &mut SearchState<'_>,
+                   pv: &mut Vec<Move>) -> Result<i32, ChessError> {
+
+        state.positions.push(board.get_hash());
+        let undo = board.make_move(mov)?;
+
+        let gives_check = (self.options.check_extensions || search.reduction > 0) && board.in_check(self.constants);
+        let extension = (self.options.check_extensions && gives_check && node.ply < CHECK_EXTENSION_PLIES * self.root_depth) as i32;
+        let search = match gives_check {
+            true => MoveSearch { reduction: 0, ..search },
+            false => search
+        };
+
+        let child = Node { depth: node.depth - 1 + extension, ply: node.ply + 1, ..node };
+        let score = self.search_after_move(board, child, search, state, pv);
+        board.unmake_move(mov, undo);
+        state.positions.pop();
+
+        score
+    }
 
-    let mut new_alpha = alpha;
-    let mut new_beta = beta;
-
-    for mov in possible_moves {
-        let (_, mov_board) = mov;
-        let mut new_board_history = board_history.clone();
-        new_board_history.push(board.clone());
-
-        let eval = minimax_move_helper(
-            &mov_board,
-            Some(board),
-            turn.opposite_color(),
-            eval_func,
-            &new_board_history,
-            constants,
-            depth - 1,
-            new_alpha,
-            new_beta,
-            alpha_beta_pruning
-        )?;
-
-        if maximizing_player {
-            if eval > ret_value {
-                ret_value = eval;
+    // Principal variation search: with null_window the move only has to be proven no better
+    // than alpha, which is cheaper than getting its score. It is only searched again with the
+    // whole window if it turns out better after all. A reduced move has to do so at the reduced
+    // depth first. The window of the node is from the point of view of the side that made the move
+    fn search_after_move(&self,
+                         board: &mut T,
+                         node: Node,
+                         search: MoveSearch,
+                         state: &mut SearchState<'_>,
+                         pv: &mut Vec<Move>) -> Result<i32, ChessError> {
+
+        let Node { alpha, beta, .. } = node;
+
+        // Without pruning the window doesn't matter, as every move gets an exact score
+        if !self.options.alpha_beta_pruning {
+            return Ok(-self.negamax(board, node.window(-INFINITY, INFINITY), true, state, pv)?);
+        }
+
+        if search.reduction > 0 {
+            let reduced = Node { depth: node.depth - search.reduction, ..node.window(-alpha - 1, -alpha) };
+            let score = -self.negamax(board, reduced, true, state, pv)?;
+            if score <= alpha {
+                return Ok(score);
             }
-            if eval > new_alpha {
-                new_alpha = eval;
+        }
+
+        if search.null_window {
+            let score = -self.negamax(board, node.window(-alpha - 1, -alpha), true, state, pv)?;
+            if score <= alpha || score >= beta {
+                return Ok(score);
             }
-        } else {
-            if eval < ret_value {
-                ret_value = eval;
+        }
+
+        Ok(-self.negamax(board, node.window(-beta, -alpha), true, state, pv)?)
+    }
+
+    // The best line found from this position is written to pv. Two null moves in a row would
+    // just search the same position shallower, so null_move is false right after one
+    fn negamax(&self,
+               board: &mut T,
+               node: Node,
+               null_move: bool,
+               state: &mut SearchState<'_>,
+               pv: &mut Vec<Move>) -> Result<i32, ChessError> {
+
+        let Node { depth, ply, alpha, beta } = node;
+
+        if self.stop.load(Ordering::Relaxed) {
+            return Err(ChessError::SearchStopped);
+        }
+        self.stats.nodes.fetch_add(1, Ordering::Relaxed);
+        pv.clear();
+
+        // The side that went back to an earlier position can do so again, so with best play it
+        // is a draw. Not at the root, which has to find a move
+        if ply > 0 && is_repetition(board, &state.positions) {
+            return Ok(0);
+        }
+
+        // Captures are played out first, so the position isn't scored with a piece still hanging
+        if depth == 0 {
+            return self.quiescence(board, ply, alpha, beta);
+        }
+
+        let mut new_alpha = alpha;
+        let mut new_beta = beta;
+
+        // A stored result from a search at least as deep can be used directly, or narrows the
+        // window. Not in the nodes searched with a wider window than the null window, as those
+        // make up the principal variation, which would be cut short
+        let pv_node = alpha + 1 < beta;
+        let hash = board.get_hash();
+        let tt_entry = self.tt.probe(hash);
+        if let Some(entry) = tt_entry.filter(|entry| entry.depth >= depth && !pv_node) {
+            let score = score_from_tt(entry.score, ply);
+            match entry.bound {
+                Bound::Exact => return Ok(score),
+                Bound::Lower => new_alpha = new_alpha.max(score),
+                Bound::Upper => new_beta = new_beta.min(score)
             }
-            if eval < new_beta {
-                new_beta = eval;
+
+            if new_beta <= new_alpha {
+                return Ok(score);
             }
         }
 
-        if alpha_beta_pruning && new_beta <= new_alpha {
-            break;
+        let turn = board.get_turn();
+        let mut possible_moves = board.generate_moves(self.constants)?;
+
+        if possible_moves.is_empty() {
+            return self.evaluate(board, ply);
+        }
+
+        let mut best_score = -INFINITY;
+        let mut best_move = None;
+        let mut child_pv = Vec::new();
+
+        // Null-move and futility pruning only show that a position isn't better than the window,
+        // so they are left out of the principal variation
+        let pruning = self.options.alpha_beta_pruning && !pv_node;
+        let in_check = board.in_check(self.constants);
+
+        if pruning && null_move && self.options.null_move_pruning && !in_check && depth >= NULL_MOVE_MIN_DEPTH
+            && board.has_non_pawn_material(turn) && !is_mate_score(beta) {
+
+            // The positions before passing can't be reached again in a real game
+            let positions = std::mem::take(&mut state.positions);
+            let undo = board.make_null_move();
+            let null_node = Node { depth: depth - 1 - NULL_MOVE_REDUCTION, ply: ply + 1, alpha: -beta, beta: -beta + 1 };
+            let score = self.negamax(board, null_node, false, state, &mut child_pv);
+            board.unmake_null_move(undo);
+            state.positions = positions;
+
+            // A mate found after passing doesn't mean there is one, so only the bound is returned
+            if -score? >= beta {
+                return Ok(beta);
+            }
+        }
+
+        let futile = pruning && self.options.futility_pruning && !in_check && (depth as usize) < FUTILITY_MARGINS.len()
+            && !is_mate_score(alpha) && self.evaluate(board, ply)? + FUTILITY_MARGINS[depth as usize] <= alpha;
+
+        // The best move found for this position earlier is searched first, as it is likely to cause cutoffs
+        state.ordering.order_moves(&mut possible_moves, tt_entry.and_then(|entry| entry.best_move), ply, turn, self.options.move_ordering);
+
+        for (i, mov) in possible_moves.iter().copied().enumerate() {
+            let quiet = !is_tactical(mov);
+            if futile && i > 0 && quiet && !self.gives_check(board, mov)? {
+                continue;
+            }
+
+            let reduction = match self.options.alpha_beta_pruning && self.options.late_move_reductions && !in_check && quiet
+                && i >= LATE_MOVE_INDEX && depth >= LATE_MOVE_MIN_DEPTH {
+                true => 1 + (i >= 2 * LATE_MOVE_INDEX) as i32,
+                false => 0
+            };
+
+            // Only the first move gets the whole window, the others are expected to be worse
+            let score = self.search_move(board, mov, node.window(new_alpha, new_beta), MoveSearch { null_window: i > 0, reduction }, state, &mut child_pv)?;
+
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mov);
+
+                pv.clear();
+                pv.push(mov);
+                pv.extend_from_slice(&child_pv);
+            }
+            new_alpha = new_alpha.max(score);
+
+            if self.options.alpha_beta_pruning && new_beta <= new_alpha {
+                self.stats.add_cutoff(i == 0);
+                // Captures are searched early anyway
+                if self.options.move_ordering && quiet {
+                    state.ordering.add_cutoff(mov, ply, depth, turn);
+                }
+                break;
+            }
         }
+
+        self.tt.store(hash, depth, score_to_tt(best_score, ply), window_bound(best_score, alpha, beta), best_move.map(|mov: Move| mov.key()));
+
+        Ok(best_score)
+    }
+
+    fn gives_check(&self, board: &mut T, mov: Move) -> Result<bool, ChessError> {
+        let undo = board.make_move(mov)?;
+        let res = board.in_check(self.constants);
+        board.unmake_move(mov, undo);
+
+        Ok(res)
+    }
+
+    // Plays captures and promotions until the position is quiet. The side to move doesn't have
+    // to capture, so the evaluation of the position is a lower bound on the score ("standing
+    // pat"), unless it is in check and has to get out of it
+    fn quiescence(&self, board: &mut T, ply: i32, alpha: i32, beta: i32) -> Result<i32, ChessError> {
+        if self.stop.load(Ordering::Relaxed) {
+            return Err(ChessError::SearchStopped);
+        }
+        self.stats.nodes.fetch_add(1, Ordering::Relaxed);
+
+        let in_check = board.in_check(self.constants);
+
+        let mut possible_moves = match in_check {
+            true => board.generate_moves(self.constants)?,
+            false => board.generate_captures(self.constants)?
+        };
+        if self.options.move_ordering {
+            order_captures(&mut possible_moves);
+        }
+
+        // The evaluation function also scores positions where the game has ended
+        let mut best_score = match in_check && !possible_moves.is_empty() {
+            true => -INFINITY,
+            false => self.evaluate(board, ply)?
+        };
+
+        let mut new_alpha = alpha.max(best_score);
+        if self.options.alpha_beta_pruning && beta <= new_alpha {
+            return Ok(best_score);
+        }
+
+        for (i, mov) in possible_moves.iter().copied().enumerate() {
+            let undo = board.make_move(mov)?;
+            let score = self.quiescence(board, ply + 1, -beta, -new_alpha);
+            board.unmake_move(mov, undo);
+            let score = -score?;
+
+            best_score = best_score.max(score);
+            new_alpha = new_alpha.max(score);
+
+            if self.options.alpha_beta_pruning && beta <= new_alpha {
+                self.stats.add_cutoff(i == 0);
+                break;
+            }
+        }
+
+        Ok(best_score)
     }
-    
-    Ok(ret_value)
 }
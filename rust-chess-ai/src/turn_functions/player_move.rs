@@ -4,9 +4,8 @@ use crate::enums::{
     chess_error::ChessError
 };
 
-use crate::functions::{
-    validate_move_string
-};
+use crate::board_types::chess_move::Move;
+use crate::notation::parse_move;
 
 use crate::Player;
 use crate::traits::chess_board_contract::ChessBoardContract;
@@ -15,8 +14,8 @@ use std::io;
 use std::io::Write;
 use std::sync::Arc;
 
-pub fn player_move<T: ChessBoardContract>(board: &T, previous_board: Option<&T>, board_history: &Vec<T>, turn: PieceColor, player: &Player<T>, constants: &Constants) -> Result<String, ChessError> {
-    let color_str = match turn {
+pub fn player_move<T: ChessBoardContract>(board: &T, board_history: &Vec<T>, player: &Player<T>, constants: &Constants) -> Result<Move, ChessError> {
+    let color_str = match board.get_turn() {
         PieceColor::White => "White",
         PieceColor::Black => "Black"
     };
@@ -24,33 +23,21 @@ pub fn player_move<T: ChessBoardContract>(board: &T, previous_board: Option<&T>,
     println!("It is {}'s turn! (You)\n", color_str);
     println!("{}", board.board_ascii(true));
 
-    let move_str = loop {
-        print!("\nEnter your move: ");
+    let mov = loop {
+        print!("\nEnter your move (like Nf3, exd5, O-O or e2 e4): ");
         std::io::stdout().flush();
     
         let mut inp = String::new();
         io::stdin().read_line(&mut inp);
 
-        if inp.len() < 5 {
-            print!("Invalid string!");
-            continue;
-        }
-        inp = inp[0..5].to_string();
-
-        let valid_move = validate_move_string(&inp);
-        if !valid_move {
-            print!("Invalid string!");
-            continue;
+        match parse_move(board, &inp, constants) {
+            Ok(mov) => break mov,
+            Err(ChessError::InvalidMoveString) => print!("Invalid string!"),
+            Err(ChessError::InvalidMove) => print!("Invalid move!"),
+            Err(ChessError::AmbiguousMove) => print!("Ambiguous move, add the file or rank the piece moves from!"),
+            Err(err) => return Err(err)
         }
-
-        let valid_move = board.generate_moves(previous_board, turn, constants)?.iter().any(|mov| (*mov).0 == inp);
-        if !valid_move {
-            print!("Invalid move!");
-            continue;
-        }
-
-        break inp;
     };
     
-    Ok(move_str)
+    Ok(mov)
 }
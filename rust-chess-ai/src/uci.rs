@@ -0,0 +1,298 @@
+use crate::board_types::bitboard::{BitBoard, Constants};
+use crate::enums::{
+    piece_color::PieceColor,
+    chess_error::ChessError,
+    evaluation_type::{EvaluationType, EVALUATION_TYPES}
+};
+use crate::notation::move_from_uci;
+use crate::evaluation_functions::score::mate_in_moves;
+use crate::traits::chess_board_contract::ChessBoardContract;
+use crate::search::iterative_deepening::{iterative_deepening, time_budget, SearchLimits, SearchParams, MAX_DEPTH};
+use crate::search::transposition_table::TranspositionTable;
+use crate::search::search_options::SearchOptions;
+use crate::search::search_threads::SearchThreads;
+
+use std::io::BufRead;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+const ENGINE_NAME: &str = "rust-chess-ai";
+const ENGINE_AUTHOR: &str = "Aimoboy";
+// Transposition table size in megabytes
+const DEFAULT_HASH_SIZE: usize = 64;
+const MAX_HASH_SIZE: usize = 4096;
+const MAX_THREADS: usize = 256;
+const DEFAULT_EVALUATION: EvaluationType = EvaluationType::PieceSquare;
+// Kept in reserve for the time it takes to send the move back to the GUI
+const MOVE_OVERHEAD: Duration = Duration::from_millis(50);
+// For a go command without a depth, time or clock the engine understands, like "go nodes 1000",
+// so it still sends a move by itself
+const FALLBACK_MOVE_TIME: Duration = Duration::from_secs(5);
+// How often a finished "go infinite" search checks whether it has been stopped
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+// With infinite, the best move is only sent once the search is stopped, even if it is done sooner
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GoCommand {
+    pub limits: SearchLimits,
+    pub infinite: bool
+}
+
+struct RunningSearch {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>
+}
+
+// Reads UCI commands from stdin until "quit" or the end of the input
+pub fn uci_loop() {
+    let constants = Arc::new(Constants::new());
+    // None after an invalid position command, so there is nothing to search until a valid one
+    let mut history: Option<Vec<BitBoard>> = Some(vec![BitBoard::new_board()]);
+    let mut running_search: Option<RunningSearch> = None;
+    let mut tt = Arc::new(TranspositionTable::new(DEFAULT_HASH_SIZE));
+    let mut evaluation = DEFAULT_EVALUATION;
+    // The helper threads are kept between searches and only replaced when the option changes
+    let mut threads = num_cpus::get().clamp(1, MAX_THREADS);
+    let mut helpers = start_helpers(threads, &constants);
+
+    let stdin = std::io::stdin();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+
+        match tokens.first() {
+            Some(&"uci") => {
+                println!("id name {}", ENGINE_NAME);
+                println!("id author {}", ENGINE_AUTHOR);
+                println!("option name Hash type spin default {} min 1 max {}", DEFAULT_HASH_SIZE, MAX_HASH_SIZE);
+                println!("option name Threads type spin default {} min 1 max {}", threads, MAX_THREADS);
+                let evaluation_names: Vec<String> = EVALUATION_TYPES.iter().map(|typ| format!("var {}", typ.get_string())).collect();
+                println!("option name Evaluation type combo default {} {}", DEFAULT_EVALUATION.get_string(), evaluation_names.join(" "));
+                println!("uciok");
+            },
+            Some(&"isready") => println!("readyok"),
+            Some(&"setoption") => {
+                stop_search(&mut running_search);
+                if let Some(size) = parse_hash_option(&tokens[1..]) {
+                    tt = Arc::new(TranspositionTable::new(size.clamp(1, MAX_HASH_SIZE)));
+                    // The size gets rounded down to a power of two
+                    println!("info string hash size {} MB", tt.size_mb());
+                }
+                if let Some(typ) = parse_evaluation_option(&tokens[1..]) {
+                    evaluation = typ;
+                }
+                if let Some(count) = parse_threads_option(&tokens[1..]) {
+                    threads = count.clamp(1, MAX_THREADS);
+                    helpers = start_helpers(threads, &constants);
+                }
+            },
+            Some(&"ucinewgame") => {
+                stop_search(&mut running_search);
+                history = Some(vec![BitBoard::new_board()]);
+                tt.clear();
+            },
+            Some(&"position") => {
+                stop_search(&mut running_search);
+                history = match parse_position(&tokens[1..], &constants) {
+                    Ok(new_history) => Some(new_history),
+                    Err(err) => {
+                        println!("info string invalid position: {:?}", err);
+                        None
+                    }
+                };
+            },
+            Some(&"go") => {
+                stop_search(&mut running_search);
+                let history = match &history {
+                    Some(history) => history,
+                    None => {
+                        println!("info string no valid position to search");
+                        println!("bestmove 0000");
+                        continue;
+                    }
+                };
+                let turn = history[history.len() - 1].get_turn();
+                let go = parse_go(&tokens[1..], turn);
+                let options = SearchOptions { threads, ..SearchOptions::default() };
+                running_search = Some(start_search(history.clone(), go, evaluation, options, constants.clone(), tt.clone(), helpers.clone()));
+            },
+            Some(&"stop") => stop_search(&mut running_search),
+            Some(&"quit") => {
+                stop_search(&mut running_search);
+                break;
+            },
+            // Unknown commands are ignored, as the protocol asks for
+            _ => ()
+        }
+    }
+
+    stop_search(&mut running_search);
+}
+
+fn stop_search(running_search: &mut Option<RunningSearch>) {
+    if let Some(running) = running_search.take() {
+        running.stop.store(true, Ordering::Relaxed);
+        let _ = running.handle.join();
+    }
+}
+
+// "startpos" or "fen <fen>", optionally followed by "moves <move> <move> ..."
+pub fn parse_position(tokens: &[&str], constants: &Constants) -> Result<Vec<BitBoard>, ChessError> {
+    let moves_index = tokens.iter().position(|token| *token == "moves").unwrap_or(tokens.len());
+
+    let start_board = match tokens.first() {
+        Some(&"startpos") => BitBoard::new_board(),
+        Some(&"fen") => BitBoard::from_fen(&tokens[1..moves_index].join(" "))?,
+        _ => return Err(ChessError::InvalidFenFieldCount)
+    };
+
+    let mut history = vec![start_board];
+
+    for mov in tokens.iter().skip(moves_index + 1) {
+        let current_board = history[history.len() - 1];
+        let new_board = current_board.after_move(move_from_uci(&current_board, mov, constants)?)?;
+        history.push(new_board);
+    }
+
+    Ok(history)
+}
+
+// "name Hash value <size>"
+pub fn parse_hash_option(tokens: &[&str]) -> Option<usize> {
+    match tokens {
+        ["name", name, "value", value] if name.eq_ignore_ascii_case("hash") => value.parse().ok(),
+        _ => None
+    }
+}
+
+// One thread less than the search uses, as the search itself runs on its own thread
+fn start_helpers(threads: usize, constants: &Arc<Constants>) -> Option<Arc<SearchThreads>> {
+    (threads > 1).then(|| Arc::new(SearchThreads::new(threads - 1, constants.clone())))
+}
+
+// "name Threads value <count>"
+pub fn parse_threads_option(tokens: &[&str]) -> Option<usize> {
+    match tokens {
+        ["name", name, "value", value] if name.eq_ignore_ascii_case("threads") => value.parse().ok(),
+        _ => None
+    }
+}
+
+// "name Evaluation value <name>"
+pub fn parse_evaluation_option(tokens: &[&str]) -> Option<EvaluationType> {
+    match tokens {
+        ["name", name, "value", value] if name.eq_ignore_ascii_case("evaluation") => EvaluationType::from_string(value),
+        _ => None
+    }
+}
+
+pub fn parse_go(tokens: &[&str], turn: PieceColor) -> GoCommand {
+    // Some GUIs send a negative time when the clock has run past zero, which counts as no time left
+    let value = |name: &str| -> Option<u64> {
+        let index = tokens.iter().position(|token| *token == name)?;
+        tokens.get(index + 1)?.parse::<i64>().ok().map(|value| value.max(0) as u64)
+    };
+
+    let depth = value("depth").map(|depth| depth as i32);
+
+    let (time_left, increment) = match turn {
+        PieceColor::White => (value("wtime"), value("winc").unwrap_or(0)),
+        PieceColor::Black => (value("btime"), value("binc").unwrap_or(0))
+    };
+
+    let infinite = tokens.contains(&"infinite");
+    let time = if infinite {
+        None
+    } else if let Some(movetime) = value("movetime") {
+        Some(Duration::from_millis(movetime))
+    } else if let Some(time_left) = time_left {
+        let moves_to_go = value("movestogo").map(|moves_to_go| moves_to_go as u32);
+        Some(time_budget(Duration::from_millis(time_left), Duration::from_millis(increment), moves_to_go))
+    } else if depth.is_none() {
+        Some(FALLBACK_MOVE_TIME)
+    } else {
+        None
+    };
+
+    GoCommand {
+        limits: SearchLimits {
+            depth: depth.unwrap_or(MAX_DEPTH),
+            time: time.map(|time| time.saturating_sub(MOVE_OVERHEAD).max(Duration::from_millis(1)))
+        },
+        infinite
+    }
+}
+
+// "cp <centipawns>", or "mate <moves>" which is negative if the engine is getting mated
+fn score_string(score: i32) -> String {
+    match mate_in_moves(score) {
+        Some(moves) => format!("mate {}", moves),
+        None => format!("cp {}", score)
+    }
+}
+
+fn start_search(history: Vec<BitBoard>,
+                go: GoCommand,
+                evaluation: EvaluationType,
+                options: SearchOptions,
+                constants: Arc<Constants>,
+                tt: Arc<TranspositionTable>,
+                helpers: Option<Arc<SearchThreads>>) -> RunningSearch {
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let search_stop = stop.clone();
+    let handle = std::thread::spawn(move || {
+        let board = &history[history.len() - 1];
+        // The positions before it, so the search can tell repetitions
+        let hashes: Vec<u64> = history[..history.len() - 1].iter().map(|board| board.get_hash()).collect();
+        let side_const = board.get_turn().side_const();
+        let params = SearchParams {
+            board,
+            history: &hashes,
+            limits: go.limits,
+            eval_func: evaluation.get_function(),
+            constants: &constants,
+            options: &options,
+            tt: &tt,
+            stop: &search_stop
+        };
+        let res = iterative_deepening(&params, helpers.as_deref(), |res, elapsed| {
+            let millis = elapsed.as_millis().max(1) as u64;
+            println!("info depth {} score {} nodes {} nps {} time {} pv {}",
+                     res.depth, score_string(res.score * side_const), res.nodes, res.nodes * 1000 / millis, millis,
+                     res.pv.iter().map(|mov| mov.to_uci()).collect::<Vec<String>>().join(" "));
+        });
+
+        // Only the GUI ends a "go infinite", also when the search is done before that
+        if go.infinite {
+            while !search_stop.load(Ordering::Relaxed) {
+                std::thread::sleep(STOP_POLL_INTERVAL);
+            }
+        }
+
+        // Play something legal even if the first iteration gets stopped
+        let best_move = match res {
+            Ok(Some(res)) => Some(res.best_move),
+            _ => match board.generate_moves(&constants) {
+                Ok(moves) => moves.first().copied(),
+                Err(_) => None
+            }
+        };
+
+        match best_move {
+            Some(mov) => println!("bestmove {}", mov.to_uci()),
+            None => println!("bestmove 0000")
+        }
+    });
+
+    RunningSearch {
+        stop,
+        handle
+    }
+}
+